@@ -1,7 +1,17 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use chrono::Utc;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use super::config;
+use super::daemon;
+use super::mock;
+use super::notifications;
+use super::prices::{self, PriceData};
+use super::recovery;
+use crate::AppState;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -11,9 +21,150 @@ pub struct Alert {
     pub condition: String,
     pub target_price: f64,
     pub created_at: i64,
+    /// Missing on alerts written by the MCP `set_alert` tool
+    #[serde(default)]
     pub triggered: bool,
     pub triggered_at: Option<i64>,
     pub exchange: Option<String>,
+    /// Disabled alerts are skipped by the evaluator but keep their config
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// When the evaluator last checked this alert; written to disk at most every
+    /// `EVALUATION_PERSIST_INTERVAL_MS`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_evaluated_at: Option<i64>,
+    /// The price the alert was last checked against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_evaluated_price: Option<f64>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertTriggered {
+    pub alert: Alert,
+    pub price: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggeredEvent {
+    pub alert_id: String,
+    pub symbol: String,
+    pub condition: String,
+    pub target: f64,
+    pub trigger_price: f64,
+    pub time: i64,
+}
+
+/// Whether alert firing is suspended because the price feed looks unreliable.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertEvaluationState {
+    pub suspended: bool,
+    pub reason: Option<String>,
+    pub since: Option<i64>,
+    /// Price anomalies within the trailing window
+    pub recent_anomalies: u32,
+    pub consecutive_failures: u32,
+}
+
+// Suspend when the feed produces this many anomalies within the window...
+const ANOMALY_WINDOW_MS: i64 = 5 * 60_000;
+const MAX_RECENT_ANOMALIES: usize = 5;
+// ...or this many fetches in a row fail (~30s at the normal poll interval)
+const MAX_CONSECUTIVE_FAILURES: u32 = 6;
+
+/// Tracks feed health for the price loop and decides when alerts are suspended.
+#[derive(Debug, Default)]
+pub struct AlertGuard {
+    anomaly_times: VecDeque<i64>,
+    state: AlertEvaluationState,
+}
+
+impl AlertGuard {
+    /// Record one price loop tick. Returns the new state if suspension toggled.
+    pub fn record_tick(&mut self, now: i64, anomalies: usize, fetch_failed: bool) -> Option<AlertEvaluationState> {
+        self.anomaly_times.extend(std::iter::repeat_n(now, anomalies));
+        while self.anomaly_times.front().is_some_and(|t| now - t > ANOMALY_WINDOW_MS) {
+            self.anomaly_times.pop_front();
+        }
+        self.state.recent_anomalies = self.anomaly_times.len() as u32;
+        self.state.consecutive_failures = if fetch_failed { self.state.consecutive_failures + 1 } else { 0 };
+        
+        let reason = if self.state.consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+            Some(format!("{} consecutive price fetches failed", self.state.consecutive_failures))
+        } else if self.anomaly_times.len() >= MAX_RECENT_ANOMALIES {
+            Some(format!("{} price anomalies in the last 5 minutes", self.anomaly_times.len()))
+        } else {
+            None
+        };
+        
+        let was_suspended = self.state.suspended;
+        self.state.suspended = reason.is_some();
+        if self.state.suspended {
+            // Keep the original start time, but refresh the reason
+            self.state.since = self.state.since.or(Some(now));
+        } else {
+            self.state.since = None;
+        }
+        self.state.reason = reason;
+        
+        (was_suspended != self.state.suspended).then(|| self.state.clone())
+    }
+    
+    pub fn is_suspended(&self) -> bool {
+        self.state.suspended
+    }
+}
+
+/// Latest evaluation per alert, held in memory between throttled writes to alerts.json.
+#[derive(Debug, Default)]
+pub struct EvaluationLog {
+    /// Alert id → (evaluated at ms, price)
+    latest: HashMap<String, (i64, f64)>,
+    persisted_at: i64,
+}
+
+// Evaluation runs every price tick; rewriting alerts.json that often isn't worth it
+const EVALUATION_PERSIST_INTERVAL_MS: i64 = 60_000;
+
+/// Why an alert has or hasn't fired, for debugging a seemingly stuck alert.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertDiagnostics {
+    pub alert_id: String,
+    pub symbol: String,
+    pub condition: String,
+    pub target_price: f64,
+    pub last_evaluated_at: Option<i64>,
+    pub last_evaluated_price: Option<f64>,
+    pub secs_since_evaluated: Option<i64>,
+    pub current_price: f64,
+    /// Target minus current price; the move still needed to trigger
+    pub distance: f64,
+    pub distance_percent: f64,
+    /// Set when the evaluator is skipping this alert (disabled, triggered, suspended)
+    pub skipped_reason: Option<String>,
+}
+
+/// Naive straight-line projection of when an alert's target would be reached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeToTarget {
+    pub alert_id: String,
+    pub symbol: String,
+    pub current_price: f64,
+    pub target_price: f64,
+    /// Fitted trend of daily closes, in price units per day
+    pub slope_per_day: f64,
+    pub days: f64,
+    pub eta: i64,
+    pub lookback_days: u32,
+    pub note: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +172,14 @@ struct AlertsFile {
     alerts: Vec<Alert>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AlertHistoryFile {
+    events: Vec<TriggeredEvent>,
+}
+
+// Rolling window for alert-history.json; oldest events are dropped first
+const MAX_HISTORY_EVENTS: usize = 1000;
+
 fn get_omnitrade_dir() -> PathBuf {
     let home = dirs::home_dir().expect("Could not find home directory");
     home.join(".omnitrade")
@@ -30,20 +189,81 @@ fn get_alerts_path() -> PathBuf {
     get_omnitrade_dir().join("alerts.json")
 }
 
-fn load_alerts() -> Result<Vec<Alert>, String> {
-    let alerts_path = get_alerts_path();
+/// Map legacy condition spellings (">", "gt", ...) to the ones `condition_met` understands.
+fn canonical_condition(condition: &str) -> Option<&'static str> {
+    match condition.trim().to_lowercase().as_str() {
+        "above" | ">" | ">=" | "gt" | "gte" | "over" => Some("above"),
+        "below" | "<" | "<=" | "lt" | "lte" | "under" => Some("below"),
+        _ => None,
+    }
+}
+
+/// Canonicalize every alert's condition, dropping (with a warning) any that can't be.
+/// Also reports whether the file needs rewriting and whether any alert was dropped.
+fn normalize_conditions(alerts: Vec<Alert>) -> (Vec<Alert>, bool, bool) {
+    let (mut changed, mut dropped) = (false, false);
+    let mut normalized = Vec::with_capacity(alerts.len());
+    
+    for mut alert in alerts {
+        match canonical_condition(&alert.condition) {
+            Some(canonical) => {
+                if alert.condition != canonical {
+                    alert.condition = canonical.to_string();
+                    changed = true;
+                }
+                normalized.push(alert);
+            }
+            None => {
+                eprintln!(
+                    "Skipping alert {} on {}: unknown condition {:?}",
+                    alert.id, alert.symbol, alert.condition
+                );
+                changed = true;
+                dropped = true;
+            }
+        }
+    }
     
+    (normalized, changed, dropped)
+}
+
+fn load_alerts_from(alerts_path: &Path) -> Result<Vec<Alert>, String> {
     if !alerts_path.exists() {
         return Ok(vec![]);
     }
     
-    let content = fs::read_to_string(&alerts_path).map_err(|e| e.to_string())?;
-    let file: AlertsFile = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    let content = fs::read_to_string(alerts_path).map_err(|e| e.to_string())?;
+    let file: Option<AlertsFile> = recovery::parse_or_quarantine(alerts_path, &content)?;
+    
+    let (alerts, changed, dropped) = normalize_conditions(file.map(|f| f.alerts).unwrap_or_default());
+    // A running daemon reads and writes the file too, so leave it alone until it stops
+    if changed && !daemon::is_daemon_running() {
+        // Keep the original when alerts are dropped so they can be restored by hand
+        if dropped {
+            let mut backup = alerts_path.as_os_str().to_owned();
+            backup.push(format!(".bak.{}", Utc::now().timestamp_millis()));
+            fs::write(PathBuf::from(backup), &content).map_err(|e| e.to_string())?;
+        }
+        write_alerts(alerts_path, &alerts)?;
+    }
     
-    Ok(file.alerts)
+    Ok(alerts)
+}
+
+pub(crate) fn load_alerts() -> Result<Vec<Alert>, String> {
+    load_alerts_from(&get_alerts_path())
+}
+
+fn write_alerts(alerts_path: &Path, alerts: &[Alert]) -> Result<(), String> {
+    let file = AlertsFile {
+        alerts: alerts.to_vec(),
+    };
+    
+    let content = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    fs::write(alerts_path, content).map_err(|e| e.to_string())
 }
 
-fn save_alerts(alerts: &[Alert]) -> Result<(), String> {
+pub(crate) fn save_alerts(alerts: &[Alert]) -> Result<(), String> {
     let alerts_path = get_alerts_path();
     let omnitrade_dir = get_omnitrade_dir();
     
@@ -52,35 +272,288 @@ fn save_alerts(alerts: &[Alert]) -> Result<(), String> {
         fs::create_dir_all(&omnitrade_dir).map_err(|e| e.to_string())?;
     }
     
-    let file = AlertsFile {
-        alerts: alerts.to_vec(),
-    };
+    write_alerts(&alerts_path, alerts)
+}
+
+fn get_history_path() -> PathBuf {
+    get_omnitrade_dir().join("alert-history.json")
+}
+
+fn load_alert_history() -> Result<Vec<TriggeredEvent>, String> {
+    let history_path = get_history_path();
+    
+    if !history_path.exists() {
+        return Ok(vec![]);
+    }
+    
+    let content = fs::read_to_string(&history_path).map_err(|e| e.to_string())?;
+    let file: Option<AlertHistoryFile> = recovery::parse_or_quarantine(&history_path, &content)?;
     
+    Ok(file.map(|f| f.events).unwrap_or_default())
+}
+
+fn append_alert_history(event: TriggeredEvent) -> Result<(), String> {
+    let mut events = load_alert_history()?;
+    events.push(event);
+    if events.len() > MAX_HISTORY_EVENTS {
+        events.drain(..events.len() - MAX_HISTORY_EVENTS);
+    }
+    
+    let omnitrade_dir = get_omnitrade_dir();
+    
+    // Ensure directory exists
+    if !omnitrade_dir.exists() {
+        fs::create_dir_all(&omnitrade_dir).map_err(|e| e.to_string())?;
+    }
+    
+    let file = AlertHistoryFile { events };
     let content = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
-    fs::write(&alerts_path, content).map_err(|e| e.to_string())?;
+    fs::write(get_history_path(), content).map_err(|e| e.to_string())?;
     
     Ok(())
 }
 
+/// A fresh, armed alert with a unique id.
+pub(crate) fn new_alert(symbol: String, condition: String, target_price: f64) -> Alert {
+    Alert {
+        id: format!("alert_{}_{}", Utc::now().timestamp_millis(), generate_id()),
+        symbol,
+        condition,
+        target_price,
+        created_at: Utc::now().timestamp_millis(),
+        triggered: false,
+        triggered_at: None,
+        exchange: Some("binance".to_string()),
+        enabled: true,
+        last_evaluated_at: None,
+        last_evaluated_price: None,
+    }
+}
+
+fn same_symbol(a: &str, b: &str) -> bool {
+    a.replace('/', "").eq_ignore_ascii_case(&b.replace('/', ""))
+}
+
+pub(crate) fn condition_met(alert: &Alert, price: f64) -> bool {
+    match alert.condition.as_str() {
+        "above" => price >= alert.target_price,
+        "below" => price <= alert.target_price,
+        _ => false,
+    }
+}
+
+/// Mark an alert triggered, notify the frontend, and dispatch notifications.
+///
+/// `record_history` is false for simulated triggers, which shouldn't show up as real events.
+async fn trigger_alert(app: &AppHandle, alert: &mut Alert, price: f64, record_history: bool) {
+    alert.triggered = true;
+    alert.triggered_at = Some(Utc::now().timestamp_millis());
+    
+    // Kept separately so the record survives the alert being removed or re-armed
+    if record_history {
+        if let Err(e) = append_alert_history(TriggeredEvent {
+            alert_id: alert.id.clone(),
+            symbol: alert.symbol.clone(),
+            condition: alert.condition.clone(),
+            target: alert.target_price,
+            trigger_price: price,
+            time: alert.triggered_at.unwrap_or_default(),
+        }) {
+            eprintln!("Failed to record alert history: {}", e);
+        }
+    }
+    
+    let _ = app.emit("alert-triggered", AlertTriggered {
+        alert: alert.clone(),
+        price,
+    });
+    
+    notifications::dispatch_alert(app, &alert.symbol, notifications::QueuedAlert {
+        condition: alert.condition.clone(),
+        target: alert.target_price,
+        price,
+    }).await;
+}
+
+/// Check untriggered alerts against the latest prices, firing any that are met.
+///
+/// Does nothing while alerts are suspended, mock prices are active, or the CLI daemon
+/// is running (it evaluates the same alerts.json, so both would notify and race on
+/// saving). Returns the alerts that triggered on this pass.
+pub(crate) async fn evaluate_alerts(app: &AppHandle, prices: &[PriceData]) -> Result<Vec<Alert>, String> {
+    if mock::is_active() || daemon::is_daemon_running() {
+        return Ok(vec![]);
+    }
+    
+    // The feed is unreliable right now; firing would likely be a false trigger
+    if let Some(state) = app.try_state::<AppState>() {
+        if state.alert_guard.lock().await.is_suspended() {
+            return Ok(vec![]);
+        }
+    }
+    
+    let mut alerts = load_alerts()?;
+    let mut fired = Vec::new();
+    let now = Utc::now().timestamp_millis();
+    
+    for alert in alerts.iter_mut().filter(|a| a.enabled && !a.triggered) {
+        let price = match prices.iter().find(|p| same_symbol(&p.symbol, &alert.symbol)) {
+            Some(p) => p.price,
+            None => continue,
+        };
+        alert.last_evaluated_at = Some(now);
+        alert.last_evaluated_price = Some(price);
+        if condition_met(alert, price) {
+            trigger_alert(app, alert, price, true).await;
+            fired.push(alert.clone());
+        }
+    }
+    
+    // Evaluation times are kept in memory every tick but only written out periodically
+    let persist_due = match app.try_state::<AppState>() {
+        Some(state) => {
+            let mut log = state.alert_evaluations.lock().await;
+            for alert in &alerts {
+                if let (Some(at), Some(price)) = (alert.last_evaluated_at, alert.last_evaluated_price) {
+                    log.latest.insert(alert.id.clone(), (at, price));
+                }
+            }
+            let due = now - log.persisted_at >= EVALUATION_PERSIST_INTERVAL_MS;
+            if due || !fired.is_empty() {
+                log.persisted_at = now;
+            }
+            due
+        }
+        None => false,
+    };
+    
+    if !fired.is_empty() || persist_due {
+        // Triggering awaits notifications; reload so alerts added or edited meanwhile are kept
+        let mut latest = load_alerts()?;
+        for alert in alerts.iter().filter(|a| a.last_evaluated_at == Some(now)) {
+            let Some(existing) = latest.iter_mut().find(|a| a.id == alert.id) else { continue };
+            existing.last_evaluated_at = alert.last_evaluated_at;
+            existing.last_evaluated_price = alert.last_evaluated_price;
+            if fired.iter().any(|f| f.id == alert.id) {
+                existing.triggered = alert.triggered;
+                existing.triggered_at = alert.triggered_at;
+            }
+        }
+        save_alerts(&latest)?;
+    }
+    Ok(fired)
+}
+
+/// Run the evaluation pass right away against freshly fetched prices.
+///
+/// Returns the alerts that triggered.
+#[tauri::command]
+pub async fn evaluate_alerts_now(app: AppHandle) -> Result<Vec<Alert>, String> {
+    mock::ensure_inactive("Alert evaluation")?;
+    if daemon::is_daemon_running() {
+        return Err("The daemon is running and evaluates alerts itself".to_string());
+    }
+    
+    let mut symbols: Vec<String> = load_alerts()?
+        .iter()
+        .filter(|a| a.enabled && !a.triggered)
+        .map(|a| prices::to_binance_symbol(&a.symbol))
+        .collect();
+    symbols.sort();
+    symbols.dedup();
+    
+    if symbols.is_empty() {
+        return Ok(vec![]);
+    }
+    
+    let price_data = prices::fetch_prices_from_binance(&symbols).await?;
+    evaluate_alerts(&app, &price_data).await
+}
+
+#[tauri::command]
+pub async fn get_alert_evaluation_state(state: State<'_, AppState>) -> Result<AlertEvaluationState, String> {
+    Ok(state.alert_guard.lock().await.state.clone())
+}
+
+/// When an alert was last evaluated, at what price, and how far it still is from its target.
+#[tauri::command]
+pub async fn get_alert_diagnostics(state: State<'_, AppState>, id: String) -> Result<AlertDiagnostics, String> {
+    let alert = load_alerts()?
+        .into_iter()
+        .find(|a| a.id == id)
+        .ok_or_else(|| format!("Alert not found: {}", id))?;
+    
+    // The in-memory log is fresher than alerts.json between throttled writes
+    let (last_evaluated_at, last_evaluated_price) = match state.alert_evaluations.lock().await.latest.get(&id) {
+        Some((at, price)) => (Some(*at), Some(*price)),
+        None => (alert.last_evaluated_at, alert.last_evaluated_price),
+    };
+    
+    let cached = state
+        .prices_cache
+        .lock()
+        .await
+        .iter()
+        .find(|p| same_symbol(&p.symbol, &alert.symbol))
+        .map(|p| p.price);
+    let current_price = match cached {
+        Some(price) => price,
+        None => prices::fetch_prices_from_binance(&[prices::to_binance_symbol(&alert.symbol)])
+            .await?
+            .first()
+            .map(|p| p.price)
+            .ok_or_else(|| format!("No price available for {}", alert.symbol))?,
+    };
+    
+    let suspended_reason = {
+        let guard = state.alert_guard.lock().await;
+        if guard.is_suspended() { guard.state.reason.clone() } else { None }
+    };
+    let skipped_reason = if !alert.enabled {
+        Some("Alert is disabled".to_string())
+    } else if alert.triggered {
+        Some("Alert has already triggered".to_string())
+    } else {
+        suspended_reason
+    };
+    
+    let distance = alert.target_price - current_price;
+    Ok(AlertDiagnostics {
+        alert_id: alert.id,
+        symbol: alert.symbol,
+        condition: alert.condition,
+        target_price: alert.target_price,
+        last_evaluated_at,
+        last_evaluated_price,
+        secs_since_evaluated: last_evaluated_at.map(|at| (Utc::now().timestamp_millis() - at) / 1000),
+        current_price,
+        distance,
+        distance_percent: if current_price > 0.0 { distance / current_price * 100.0 } else { 0.0 },
+        skipped_reason,
+    })
+}
+
 #[tauri::command]
 pub async fn get_alerts() -> Result<Vec<Alert>, String> {
     load_alerts()
 }
 
+/// Triggered alerts, most recent first.
+#[tauri::command]
+pub async fn get_alert_history(limit: Option<u32>) -> Result<Vec<TriggeredEvent>, String> {
+    let mut events = load_alert_history()?;
+    events.reverse();
+    if let Some(limit) = limit {
+        events.truncate(limit as usize);
+    }
+    Ok(events)
+}
+
 #[tauri::command]
 pub async fn add_alert(symbol: String, condition: String, price: f64) -> Result<Alert, String> {
     let mut alerts = load_alerts()?;
     
-    let new_alert = Alert {
-        id: format!("alert_{}_{}", Utc::now().timestamp_millis(), generate_id()),
-        symbol,
-        condition,
-        target_price: price,
-        created_at: Utc::now().timestamp_millis(),
-        triggered: false,
-        triggered_at: None,
-        exchange: Some("binance".to_string()),
-    };
+    let new_alert = new_alert(symbol, condition, price);
     
     alerts.push(new_alert.clone());
     save_alerts(&alerts)?;
@@ -88,6 +561,35 @@ pub async fn add_alert(symbol: String, condition: String, price: f64) -> Result<
     Ok(new_alert)
 }
 
+/// Add an alert at a level picked on the chart, inferring the direction from the market.
+///
+/// A level above the current price becomes an "above" alert, one below becomes "below",
+/// so the alert never triggers on the next evaluation.
+#[tauri::command]
+pub async fn add_alert_from_level(symbol: String, price: f64) -> Result<Alert, String> {
+    if !price.is_finite() || price <= 0.0 {
+        return Err("Price must be positive".to_string());
+    }
+    
+    let binance_symbol = prices::to_binance_symbol(&symbol);
+    let current_price = prices::fetch_prices_from_binance(std::slice::from_ref(&binance_symbol))
+        .await?
+        .into_iter()
+        .find(|p| prices::to_binance_symbol(&p.symbol) == binance_symbol)
+        .map(|p| p.price)
+        .ok_or_else(|| format!("No price available for {}", symbol))?;
+    
+    let condition = if price > current_price {
+        "above"
+    } else if price < current_price {
+        "below"
+    } else {
+        return Err(format!("Level {} is the current price of {}; pick a level above or below it", price, symbol));
+    };
+    
+    add_alert(symbol, condition.to_string(), price).await
+}
+
 #[tauri::command]
 pub async fn remove_alert(id: String) -> Result<(), String> {
     let mut alerts = load_alerts()?;
@@ -96,6 +598,109 @@ pub async fn remove_alert(id: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Enable or disable every alert on a symbol. Returns how many alerts changed.
+#[tauri::command]
+pub async fn set_alerts_enabled_for_symbol(symbol: String, enabled: bool) -> Result<u32, String> {
+    let mut alerts = load_alerts()?;
+    let mut changed = 0;
+    
+    for alert in alerts.iter_mut() {
+        if same_symbol(&alert.symbol, &symbol) && alert.enabled != enabled {
+            alert.enabled = enabled;
+            changed += 1;
+        }
+    }
+    
+    if changed > 0 {
+        save_alerts(&alerts)?;
+    }
+    Ok(changed)
+}
+
+/// Fire an alert through the real trigger path using a synthetic price.
+///
+/// Only available in debug builds or with `devMode` enabled in config.
+#[tauri::command]
+pub async fn simulate_alert(app: AppHandle, id: String) -> Result<(), String> {
+    let dev_mode = config::load_config()?.dev_mode.unwrap_or(false);
+    if !cfg!(debug_assertions) && !dev_mode {
+        return Err("simulate_alert requires a debug build or devMode".to_string());
+    }
+    
+    let mut alerts = load_alerts()?;
+    let alert = alerts
+        .iter_mut()
+        .find(|a| a.id == id)
+        .ok_or_else(|| format!("Alert not found: {}", id))?;
+    
+    // Nudge just past the target so the condition is genuinely met
+    let price = match alert.condition.as_str() {
+        "below" => alert.target_price * 0.999,
+        _ => alert.target_price * 1.001,
+    };
+    trigger_alert(&app, alert, price, false).await;
+    
+    save_alerts(&alerts)
+}
+
+// Daily closes used for the trend fit
+const TREND_LOOKBACK_DAYS: u32 = 30;
+
+/// Least-squares slope of `values` against their index.
+fn linear_slope(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+    let n = values.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+    let (mut cov, mut var) = (0.0, 0.0);
+    for (i, y) in values.iter().enumerate() {
+        let dx = i as f64 - mean_x;
+        cov += dx * (y - mean_y);
+        var += dx * dx;
+    }
+    Some(cov / var)
+}
+
+/// Extrapolate the recent daily trend to estimate when an alert would trigger.
+///
+/// Returns None if the trend is flat or heading away from the target. This is a
+/// naive projection, not a forecast.
+#[tauri::command]
+pub async fn estimate_time_to_target(alert_id: String) -> Result<Option<TimeToTarget>, String> {
+    let alert = load_alerts()?
+        .into_iter()
+        .find(|a| a.id == alert_id)
+        .ok_or_else(|| format!("Alert not found: {}", alert_id))?;
+    
+    let klines = prices::fetch_klines(&alert.symbol, "1d", None, TREND_LOOKBACK_DAYS).await?;
+    let closes: Vec<f64> = klines.iter().map(|k| k.close).collect();
+    let current_price = *closes.last().ok_or_else(|| format!("No price history for {}", alert.symbol))?;
+    let slope = linear_slope(&closes).ok_or_else(|| format!("Not enough price history for {}", alert.symbol))?;
+    
+    let distance = alert.target_price - current_price;
+    let days = if condition_met(&alert, current_price) {
+        0.0
+    } else if slope != 0.0 && distance.signum() == slope.signum() {
+        distance / slope
+    } else {
+        return Ok(None);
+    };
+    
+    Ok(Some(TimeToTarget {
+        alert_id: alert.id,
+        symbol: alert.symbol,
+        current_price,
+        target_price: alert.target_price,
+        slope_per_day: slope,
+        days,
+        eta: Utc::now().timestamp_millis() + (days * 86_400_000.0) as i64,
+        lookback_days: TREND_LOOKBACK_DAYS,
+        note: "Naive straight-line projection of the recent trend; prices rarely move in straight lines".to_string(),
+    }))
+}
+
 fn generate_id() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
     let duration = SystemTime::now()
@@ -103,3 +708,58 @@ fn generate_id() -> String {
         .unwrap();
     format!("{:x}", duration.as_nanos() & 0xFFFFFF)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn legacy_alert(id: &str, condition: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "symbol": "BTC/USDT",
+            "condition": condition,
+            "targetPrice": 50000.0,
+            "createdAt": 0,
+            "triggered": false,
+            "triggeredAt": null,
+            "exchange": "binance"
+        })
+    }
+    
+    #[test]
+    fn load_alerts_normalizes_legacy_conditions() {
+        let dir = std::env::temp_dir().join(format!("omnitrade-alerts-{}", Utc::now().timestamp_nanos_opt().unwrap_or(0)));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("alerts.json");
+        let file = serde_json::json!({
+            "alerts": [
+                legacy_alert("a", ">"),
+                legacy_alert("b", "gt"),
+                legacy_alert("c", "above"),
+                legacy_alert("d", "LT"),
+                legacy_alert("e", "<="),
+                legacy_alert("f", "sideways"),
+            ]
+        });
+        fs::write(&path, file.to_string()).unwrap();
+        
+        let alerts = load_alerts_from(&path).unwrap();
+        let conditions: Vec<(&str, &str)> = alerts.iter().map(|a| (a.id.as_str(), a.condition.as_str())).collect();
+        assert_eq!(
+            conditions,
+            vec![("a", "above"), ("b", "above"), ("c", "above"), ("d", "below"), ("e", "below")]
+        );
+        
+        // The file is rewritten in canonical form, with the original kept as a backup
+        assert_eq!(load_alerts_from(&path).unwrap().len(), 5);
+        let rewritten: AlertsFile = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(rewritten.alerts.iter().all(|a| a.condition == "above" || a.condition == "below"));
+        let backups = fs::read_dir(&dir)
+            .unwrap()
+            .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().starts_with("alerts.json.bak."))
+            .count();
+        assert_eq!(backups, 1);
+        
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,14 +1,28 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use super::recovery;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExchangeConfig {
     pub api_key: String,
     pub secret: String,
+    /// Defaults to testnet when missing, matching the TS schema
+    #[serde(default = "default_testnet")]
     pub testnet: bool,
+    /// When the credentials were last saved from the app
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub saved_at: Option<i64>,
+    /// Fields owned by the CLI/MCP side (e.g. `password`), kept so saving doesn't drop them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+pub(crate) fn default_testnet() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,14 +30,41 @@ pub struct ExchangeConfig {
 pub struct SecurityConfig {
     pub max_order_size: f64,
     pub confirm_trades: bool,
+    /// USDT that paper buys and daemon DCA buys may never spend into
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_usdt_reserve: Option<f64>,
+    /// Paper trades (desktop and CLI) allowed per local calendar day; live orders aren't counted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_trades_per_day: Option<u32>,
+    /// Fields owned by the CLI/MCP side (`allowedPairs`, `testnetOnly`, ...)
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NotificationConfig {
-    pub native: Option<bool>,
+    pub native: Option<NativeNotification>,
     pub telegram: Option<TelegramConfig>,
     pub discord: Option<DiscordConfig>,
+    /// Do Not Disturb: suppress notifications until this timestamp (ms)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dnd_until: Option<i64>,
+    /// Alerts on the same symbol firing within this many seconds are sent as one message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coalesce_window_secs: Option<u64>,
+}
+
+/// `native` as saved by the desktop (a bool) or the CLI (`{ "enabled": bool }`).
+/// Kept in whichever shape it was read so the other front-end can still parse it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum NativeNotification {
+    Flag(bool),
+    Settings {
+        #[serde(default)]
+        enabled: bool,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +73,9 @@ pub struct TelegramConfig {
     pub enabled: bool,
     pub bot_token: Option<String>,
     pub chat_id: Option<String>,
+    /// Deliver critical notifications even while DND is active
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bypass_dnd: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,14 +83,158 @@ pub struct TelegramConfig {
 pub struct DiscordConfig {
     pub enabled: bool,
     pub webhook_url: Option<String>,
+    /// Deliver critical notifications even while DND is active
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bypass_dnd: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaemonConfig {
+    pub poll_interval: Option<u64>,
+    pub log_file: Option<String>,
+}
+
+/// Parameters for drawdown-scaled ("smart") DCA amounts. Unset fields use defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartDcaConfig {
+    /// Days of history used to find the recent high (default 30)
+    pub lookback_days: Option<u32>,
+    /// Drawdown from the high at which the maximum multiplier applies (default 30%)
+    pub full_scale_drawdown_percent: Option<f64>,
+    /// Upper bound on the amount multiplier (default 2.0)
+    pub max_multiplier: Option<f64>,
+}
+
+/// Volume-scaled fill impact for realistic backtests. Unset fields use defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlippageConfig {
+    /// Slippage at a buy equal to the day's whole volume, scaled by the square root of participation (default 10%)
+    pub impact_coefficient_percent: Option<f64>,
+    /// Upper bound on per-fill slippage (default 5%)
+    pub max_slippage_percent: Option<f64>,
+}
+
+impl SlippageConfig {
+    /// Adverse price move, as a fraction, for buying `usdt` on a day that traded `day_volume_usdt`.
+    pub fn impact(&self, usdt: f64, day_volume_usdt: f64) -> f64 {
+        let max = self.max_slippage_percent.unwrap_or(5.0) / 100.0;
+        if day_volume_usdt <= 0.0 {
+            return max;
+        }
+        let k = self.impact_coefficient_percent.unwrap_or(10.0) / 100.0;
+        (k * (usdt / day_volume_usdt).sqrt()).min(max)
+    }
+}
+
+/// Largest share of the portfolio a single asset may reach through paper or daemon DCA buys.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationCaps {
+    /// Per-asset caps in percent, keyed by asset (e.g. "BTC": 25.0)
+    #[serde(default)]
+    pub max_allocation_percent: HashMap<String, f64>,
+    /// Cap for assets without their own entry (uncapped when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_max_allocation_percent: Option<f64>,
+}
+
+impl AllocationCaps {
+    pub fn cap_for(&self, asset: &str) -> Option<f64> {
+        self.max_allocation_percent
+            .get(&asset.to_uppercase())
+            .copied()
+            .or(self.default_max_allocation_percent)
+    }
+}
+
+/// Scheduled portfolio reports sent through the notification channels.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportConfig {
+    /// "daily", "weekly" or "off" (default)
+    pub report_frequency: Option<String>,
+    /// Local time of day to send, "HH:MM" (default "09:00"); weekly reports go out on Mondays
+    pub report_time: Option<String>,
+}
+
+/// Exchange trading fees as fractions (0.001 = 0.1%). Unset fields use Binance spot defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeConfig {
+    pub maker_rate: Option<f64>,
+    pub taker_rate: Option<f64>,
+}
+
+impl FeeConfig {
+    pub fn maker(&self) -> f64 {
+        self.maker_rate.unwrap_or(0.001)
+    }
+    
+    pub fn taker(&self) -> f64 {
+        self.taker_rate.unwrap_or(0.001)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Config {
     pub exchanges: HashMap<String, ExchangeConfig>,
     pub security: Option<SecurityConfig>,
     pub notifications: Option<NotificationConfig>,
+    /// Cached prices older than this are reported stale (default: 3× poll interval)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_stale_after_secs: Option<u64>,
+    /// Symbols polled by the price loop, in display order (e.g. "BTC/USDT")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watchlist: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daemon: Option<DaemonConfig>,
+    /// Enables developer-only commands in release builds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dev_mode: Option<bool>,
+    /// Also emit `price-update:<SYMBOL>` events for symbols that moved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_symbol_price_events: Option<bool>,
+    /// Minimum move (percent) before a per-symbol event is emitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_event_epsilon_percent: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smart_dca: Option<SmartDcaConfig>,
+    /// "fifo" or "average" (default) for per-asset cost basis
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_basis_method: Option<String>,
+    /// Compensate for local clock skew on signed requests (default true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_clock_sync: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fees: Option<FeeConfig>,
+    /// Replaces https://api.binance.com (and the testnet host) for REST calls, e.g. a caching proxy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binance_api_base: Option<String>,
+    /// Single-tick moves beyond this percent are reported as `price-anomaly` (off when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tick_jump_percent: Option<f64>,
+    /// Leave anomalous ticks out of alert evaluation (default true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anomaly_skip_alerts: Option<bool>,
+    /// Maximum in-flight signed requests per exchange (default 2)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_request_concurrency: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reports: Option<ReportConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slippage: Option<SlippageConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocation_caps: Option<AllocationCaps>,
+    /// Exchange used when a command isn't given one, shared with the CLI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_exchange: Option<String>,
+    /// Fields owned by the CLI/MCP side, kept so saving doesn't drop them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 fn get_omnitrade_dir() -> PathBuf {
@@ -58,36 +246,275 @@ fn get_config_path() -> PathBuf {
     get_omnitrade_dir().join("config.json")
 }
 
-fn mask_key(key: &str) -> String {
+pub(crate) fn mask_key(key: &str) -> String {
     if key.len() < 10 {
         return "***".to_string();
     }
     format!("{}...{}", &key[..5], &key[key.len()-5..])
 }
 
-#[tauri::command]
-pub async fn get_config() -> Result<Config, String> {
+pub(crate) fn default_config() -> Config {
+    Config {
+        security: Some(SecurityConfig {
+            max_order_size: 100.0,
+            confirm_trades: true,
+            min_usdt_reserve: None,
+            max_trades_per_day: None,
+            extra: Default::default(),
+        }),
+        ..Default::default()
+    }
+}
+
+/// Load the raw (unmasked) config, falling back to defaults if it doesn't exist yet.
+pub(crate) fn load_config() -> Result<Config, String> {
     let config_path = get_config_path();
     
     if !config_path.exists() {
-        // Return empty config if not exists
-        return Ok(Config {
-            exchanges: HashMap::new(),
-            security: Some(SecurityConfig {
-                max_order_size: 100.0,
-                confirm_trades: true,
-            }),
-            notifications: None,
-        });
+        return Ok(default_config());
     }
     
     let content = fs::read_to_string(&config_path).map_err(|e| e.to_string())?;
-    let mut config: Config = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    let config: Option<Config> = recovery::parse_or_quarantine(&config_path, &content)?;
+    let mut config = config.unwrap_or_else(default_config);
+    
+    if let Some(base) = config.binance_api_base.take() {
+        match validate_api_base(&base) {
+            Ok(base) => config.binance_api_base = Some(base),
+            Err(e) => eprintln!("Ignoring binanceApiBase: {}", e),
+        }
+    }
+    
+    // One-time migration of exchange names saved before they were lowercased
+    if config.exchanges.keys().any(|name| *name != name.to_lowercase()) {
+        backup_config()?;
+        merge_exchange_names(&mut config);
+        save_config(&config)?;
+    }
+    
+    Ok(config)
+}
+
+/// Require an absolute https URL and drop any trailing slash.
+fn validate_api_base(base: &str) -> Result<String, String> {
+    let url = reqwest::Url::parse(base.trim()).map_err(|e| format!("{} is not a valid URL: {}", base, e))?;
+    if url.scheme() != "https" || url.host_str().is_none() {
+        return Err(format!("{} must be an https URL", base));
+    }
+    Ok(url.as_str().trim_end_matches('/').to_string())
+}
+
+/// Collapse case-variant exchange entries into one lowercase key each.
+///
+/// The most recently saved credentials win; entries without `savedAt` lose to
+/// ones with it, and an existing lowercase key wins ties. Returns how many
+/// entries were merged away.
+fn merge_exchange_names(config: &mut Config) -> u32 {
+    let before = config.exchanges.len();
+    let mut merged: HashMap<String, (String, ExchangeConfig)> = HashMap::new();
+    
+    for (name, exchange) in config.exchanges.drain() {
+        let key = name.to_lowercase();
+        let replace = match merged.get(&key) {
+            Some((kept_name, kept)) => {
+                let (new_at, kept_at) = (exchange.saved_at.unwrap_or(0), kept.saved_at.unwrap_or(0));
+                new_at > kept_at || (new_at == kept_at && name == key && *kept_name != key)
+            }
+            None => true,
+        };
+        if replace {
+            merged.insert(key, (name, exchange));
+        }
+    }
+    
+    config.exchanges = merged.into_iter().map(|(key, (_, exchange))| (key, exchange)).collect();
+    (before - config.exchanges.len()) as u32
+}
+
+/// Store `exchange` under its lowercase `name`, replacing every case variant of it.
+///
+/// Fields only the CLI/MCP side sets (`extra`) are carried over from the most recently
+/// saved variant, so saving from the app doesn't drop e.g. a passphrase.
+pub(crate) fn upsert_exchange(config: &mut Config, name: &str, mut exchange: ExchangeConfig) {
+    let key = name.to_lowercase();
+    let variants: Vec<String> = config.exchanges.keys().filter(|k| k.eq_ignore_ascii_case(&key)).cloned().collect();
+    let newest = variants
+        .into_iter()
+        .filter_map(|k| config.exchanges.remove(&k))
+        .max_by_key(|e| e.saved_at.unwrap_or(0));
+    if let Some(previous) = newest {
+        exchange.extra = previous.extra;
+    }
+    config.exchanges.insert(key, exchange);
+}
+
+/// Look up an exchange's credentials regardless of how its name is cased.
+pub(crate) fn find_exchange<'a>(config: &'a Config, name: &str) -> Option<&'a ExchangeConfig> {
+    config
+        .exchanges
+        .get(&name.to_lowercase())
+        .or_else(|| config.exchanges.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v))
+}
+
+/// The exchange to use when none is given: `defaultExchange`, else the only one configured.
+pub(crate) fn default_exchange(config: &Config) -> Option<String> {
+    match &config.default_exchange {
+        Some(name) => Some(name.to_lowercase()),
+        None if config.exchanges.len() == 1 => config.exchanges.keys().next().cloned(),
+        None => None,
+    }
+}
+
+pub(crate) fn save_config(config: &Config) -> Result<(), String> {
+    let omnitrade_dir = get_omnitrade_dir();
+    
+    // Ensure directory exists
+    if !omnitrade_dir.exists() {
+        fs::create_dir_all(&omnitrade_dir).map_err(|e| e.to_string())?;
+    }
+    
+    let config_path = get_config_path();
+    #[cfg(unix)]
+    let created = !config_path.exists();
+    let content = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    fs::write(&config_path, content).map_err(|e| e.to_string())?;
+    
+    // Credentials end up in config.json, so keep it owner-only from the start
+    #[cfg(unix)]
+    if created {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&config_path, fs::Permissions::from_mode(0o600)).map_err(|e| e.to_string())?;
+    }
+    
+    super::exchanges::refresh_api_base(config);
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSecurity {
+    pub path: String,
+    pub exists: bool,
+    /// Octal file mode, e.g. "600" (Unix only)
+    pub file_mode: Option<String>,
+    pub dir_mode: Option<String>,
+    pub group_readable: bool,
+    pub other_readable: bool,
+    pub secure: bool,
+    pub note: Option<String>,
+}
+
+#[cfg(unix)]
+fn inspect_config_security() -> Result<ConfigSecurity, String> {
+    use std::os::unix::fs::PermissionsExt;
+    
+    let config_path = get_config_path();
+    let mode_of = |path: &Path| fs::metadata(path).ok().map(|m| m.permissions().mode() & 0o777);
+    
+    let file_mode = mode_of(&config_path);
+    let dir_mode = mode_of(&get_omnitrade_dir());
+    let group_readable = file_mode.map(|m| m & 0o040 != 0).unwrap_or(false);
+    let other_readable = file_mode.map(|m| m & 0o004 != 0).unwrap_or(false);
+    
+    Ok(ConfigSecurity {
+        path: config_path.display().to_string(),
+        exists: file_mode.is_some(),
+        file_mode: file_mode.map(|m| format!("{:o}", m)),
+        dir_mode: dir_mode.map(|m| format!("{:o}", m)),
+        group_readable,
+        other_readable,
+        secure: !group_readable && !other_readable,
+        note: None,
+    })
+}
+
+#[cfg(windows)]
+fn inspect_config_security() -> Result<ConfigSecurity, String> {
+    let config_path = get_config_path();
+    Ok(ConfigSecurity {
+        path: config_path.display().to_string(),
+        exists: config_path.exists(),
+        file_mode: None,
+        dir_mode: None,
+        group_readable: false,
+        other_readable: false,
+        secure: true,
+        note: Some("Permission checks are not supported on Windows; the file inherits your user profile ACLs".to_string()),
+    })
+}
+
+/// Copy a data file to `<name>.bak` before a destructive write.
+pub(crate) fn backup_file(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    fs::copy(path, PathBuf::from(backup)).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub(crate) fn backup_config() -> Result<(), String> {
+    backup_file(&get_config_path())
+}
+
+/// Validation failure tied to a specific input field, so the UI can highlight it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldError {
+    pub field: String,
+    pub reason: String,
+}
+
+impl FieldError {
+    fn new(field: &str, reason: impl Into<String>) -> Self {
+        FieldError {
+            field: field.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.reason)
+    }
+}
+
+/// Trim and check exchange credentials, returning the normalized (name, api_key, secret).
+pub(crate) fn validate_exchange_input(
+    name: &str,
+    api_key: &str,
+    secret: &str,
+) -> Result<(String, String, String), FieldError> {
+    let name = name.trim().to_lowercase();
+    let api_key = api_key.trim();
+    let secret = secret.trim();
+    
+    if name.is_empty() {
+        return Err(FieldError::new("name", "must not be empty"));
+    }
+    if api_key.is_empty() {
+        return Err(FieldError::new("api_key", "must not be empty"));
+    }
+    if secret.is_empty() {
+        return Err(FieldError::new("secret", "must not be empty"));
+    }
+    
+    Ok((name, api_key.to_string(), secret.to_string()))
+}
+
+#[tauri::command]
+pub async fn get_config() -> Result<Config, String> {
+    let mut config = load_config()?;
     
     // Mask sensitive fields before returning
     for (_, exchange) in config.exchanges.iter_mut() {
         exchange.api_key = mask_key(&exchange.api_key);
         exchange.secret = "********".to_string();
+        if let Some(password) = exchange.extra.get_mut("password") {
+            *password = serde_json::Value::String("********".to_string());
+        }
     }
     
     Ok(config)
@@ -99,40 +526,101 @@ pub async fn save_exchange(
     api_key: String,
     secret: String,
     testnet: bool,
-) -> Result<(), String> {
-    let config_path = get_config_path();
-    let omnitrade_dir = get_omnitrade_dir();
-    
-    // Ensure directory exists
-    if !omnitrade_dir.exists() {
-        fs::create_dir_all(&omnitrade_dir).map_err(|e| e.to_string())?;
-    }
+) -> Result<(), FieldError> {
+    let (name, api_key, secret) = validate_exchange_input(&name, &api_key, &secret)?;
     
     // Load existing config or create new
-    let mut config: Config = if config_path.exists() {
-        let content = fs::read_to_string(&config_path).map_err(|e| e.to_string())?;
-        serde_json::from_str(&content).map_err(|e| e.to_string())?
-    } else {
-        Config {
-            exchanges: HashMap::new(),
-            security: Some(SecurityConfig {
-                max_order_size: 100.0,
-                confirm_trades: true,
-            }),
-            notifications: None,
-        }
-    };
+    let mut config = load_config().map_err(|e| FieldError::new("config", e))?;
     
-    // Update or add exchange
-    config.exchanges.insert(name, ExchangeConfig {
+    // Update or add exchange, keeping fields only the CLI/MCP side sets
+    upsert_exchange(&mut config, &name, ExchangeConfig {
         api_key,
         secret,
         testnet,
+        saved_at: Some(chrono::Utc::now().timestamp_millis()),
+        extra: Default::default(),
     });
     
-    // Save config
-    let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
-    fs::write(&config_path, content).map_err(|e| e.to_string())?;
+    save_config(&config).map_err(|e| FieldError::new("config", e))
+}
+
+/// Merge exchange entries that differ only by case. Returns how many were removed.
+#[tauri::command]
+pub async fn merge_duplicate_exchanges() -> Result<u32, String> {
+    // load_config already migrates mixed-case keys; this re-runs the merge on
+    // whatever is on disk in case the file was edited since
+    let content = match fs::read_to_string(get_config_path()) {
+        Ok(content) => content,
+        Err(_) => return Ok(0),
+    };
+    let mut config: Config = serde_json::from_str(&content).map_err(|e| e.to_string())?;
     
-    Ok(())
+    let removed = merge_exchange_names(&mut config);
+    if removed > 0 {
+        backup_config()?;
+        save_config(&config)?;
+    }
+    Ok(removed)
+}
+
+#[tauri::command]
+pub async fn get_allocation_caps() -> Result<AllocationCaps, String> {
+    Ok(load_config()?.allocation_caps.unwrap_or_default())
+}
+
+/// Set the allocation cap for `asset`, or the default cap for all assets when `asset`
+/// is omitted. A `percent` of None removes the cap.
+#[tauri::command]
+pub async fn set_allocation_cap(asset: Option<String>, percent: Option<f64>) -> Result<AllocationCaps, String> {
+    if let Some(percent) = percent {
+        if !percent.is_finite() || percent <= 0.0 || percent > 100.0 {
+            return Err("Allocation cap must be above 0 and at most 100 percent".to_string());
+        }
+    }
+    
+    let mut config = load_config()?;
+    let mut caps = config.allocation_caps.unwrap_or_default();
+    match asset.map(|a| a.trim().to_uppercase()) {
+        Some(asset) if asset.is_empty() => return Err("Asset cannot be empty".to_string()),
+        Some(asset) => match percent {
+            Some(percent) => {
+                caps.max_allocation_percent.insert(asset, percent);
+            }
+            None => {
+                caps.max_allocation_percent.remove(&asset);
+            }
+        },
+        None => caps.default_max_allocation_percent = percent,
+    }
+    
+    config.allocation_caps = Some(caps.clone());
+    save_config(&config)?;
+    Ok(caps)
+}
+
+#[tauri::command]
+pub async fn check_config_security() -> Result<ConfigSecurity, String> {
+    inspect_config_security()
+}
+
+/// Restrict config.json to the owner (600) and ~/.omnitrade to 700.
+#[tauri::command]
+pub async fn harden_config_permissions() -> Result<ConfigSecurity, String> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        
+        let omnitrade_dir = get_omnitrade_dir();
+        if omnitrade_dir.exists() {
+            fs::set_permissions(&omnitrade_dir, fs::Permissions::from_mode(0o700))
+                .map_err(|e| e.to_string())?;
+        }
+        let config_path = get_config_path();
+        if config_path.exists() {
+            fs::set_permissions(&config_path, fs::Permissions::from_mode(0o600))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    
+    inspect_config_security()
 }
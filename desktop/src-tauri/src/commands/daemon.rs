@@ -3,6 +3,8 @@ use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
 
+use super::config::{self, DaemonConfig};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DaemonStatus {
@@ -11,6 +13,14 @@ pub struct DaemonStatus {
     pub uptime: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaemonConfigUpdate {
+    pub config: DaemonConfig,
+    pub restart_required: bool,
+    pub note: Option<String>,
+}
+
 fn get_omnitrade_dir() -> PathBuf {
     let home = dirs::home_dir().expect("Could not find home directory");
     home.join(".omnitrade")
@@ -45,6 +55,15 @@ fn is_process_running(pid: u32) -> bool {
     }
 }
 
+/// Whether the CLI daemon is up, per its PID file. Unlike `get_daemon_status` this
+/// leaves a stale PID file alone, so it's safe to call from background loops.
+pub(crate) fn is_daemon_running() -> bool {
+    fs::read_to_string(get_pid_path())
+        .ok()
+        .and_then(|pid| pid.trim().parse::<u32>().ok())
+        .is_some_and(is_process_running)
+}
+
 #[tauri::command]
 pub async fn get_daemon_status() -> Result<DaemonStatus, String> {
     let pid_path = get_pid_path();
@@ -171,3 +190,66 @@ pub async fn get_daemon_log(lines: u32) -> Result<Vec<String>, String> {
     
     Ok(all_lines[start..].to_vec())
 }
+
+#[tauri::command]
+pub async fn get_daemon_config() -> Result<DaemonConfig, String> {
+    Ok(config::load_config()?.daemon.unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn set_daemon_config(daemon: DaemonConfig) -> Result<DaemonConfigUpdate, String> {
+    if daemon.poll_interval == Some(0) {
+        return Err("pollInterval must be a positive number of seconds".to_string());
+    }
+    if let Some(log_file) = &daemon.log_file {
+        if log_file.trim().is_empty() {
+            return Err("logFile must not be empty".to_string());
+        }
+    }
+    
+    let mut config = config::load_config()?;
+    config.daemon = Some(daemon.clone());
+    
+    config::backup_config()?;
+    config::save_config(&config)?;
+    
+    // A running daemon keeps its old settings until reloaded (SIGHUP) or restarted
+    let running = get_daemon_status().await?.running;
+    Ok(DaemonConfigUpdate {
+        config: daemon,
+        restart_required: running,
+        note: running.then(|| "Reload or restart the daemon to apply the new settings".to_string()),
+    })
+}
+
+/// Ask a running daemon to re-read its config without restarting.
+#[tauri::command]
+pub async fn reload_daemon() -> Result<(), String> {
+    let status = get_daemon_status().await?;
+    
+    let pid = match (status.running, status.pid) {
+        (true, Some(pid)) => pid,
+        _ => return Err("Daemon is not running".to_string()),
+    };
+    
+    #[cfg(unix)]
+    {
+        let output = Command::new("kill")
+            .args(["-HUP", &pid.to_string()])
+            .output()
+            .map_err(|e| format!("Failed to signal daemon: {}", e))?;
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Failed to signal daemon: {}", stderr));
+        }
+        Ok(())
+    }
+    
+    #[cfg(windows)]
+    {
+        // No SIGHUP equivalent on Windows — the UI should offer a restart instead
+        let _ = pid;
+        Err("Live reload is not supported on Windows; restart the daemon instead".to_string())
+    }
+}
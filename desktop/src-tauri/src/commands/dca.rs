@@ -2,17 +2,88 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use super::config::{self, SlippageConfig};
+use super::prices;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DCAConfig {
     pub id: String,
     pub asset: String,
     pub amount: f64,
-    pub frequency: String, // "daily", "weekly", "monthly"
+    pub frequency: String, // "hourly", "daily", "weekly", "monthly"
     pub enabled: bool,
     pub last_run: Option<i64>,
     pub next_run: Option<i64>,
     pub executions: u32,
+    /// Scale each buy with the asset's drawdown (see `suggest_dca_amount`)
+    #[serde(default)]
+    pub smart: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetProjection {
+    pub asset: String,
+    pub annual_amount: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DcaProjection {
+    pub assets: Vec<AssetProjection>,
+    pub total_annual: f64,
+    /// Base-amount estimate for smart plans, whose actual buys scale with drawdown
+    pub smart_estimate: f64,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BacktestResult {
+    pub invested: f64,
+    pub units: f64,
+    pub average_cost: f64,
+    pub ending_value: f64,
+    pub return_percent: f64,
+    pub annualized_return_percent: f64,
+    pub buys: u32,
+    /// Extra USDT paid over the close due to modeled slippage (0 when idealized)
+    pub slippage_cost: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DcaComparison {
+    pub asset: String,
+    pub frequency: String,
+    pub from: i64,
+    pub to: i64,
+    pub dca: BacktestResult,
+    pub lump_sum: BacktestResult,
+    /// Fills were adjusted for volume-scaled slippage
+    pub realistic: bool,
+    /// Close-price fills for comparison, set when `realistic`
+    pub idealized_dca: Option<BacktestResult>,
+    pub idealized_lump_sum: Option<BacktestResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrequencyResult {
+    pub frequency: String,
+    pub result: BacktestResult,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrequencyRecommendation {
+    pub asset: String,
+    pub lookback_days: u32,
+    pub results: Vec<FrequencyResult>,
+    /// Frequency with the lowest average cost over the window
+    pub recommended: String,
+    pub note: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,7 +100,7 @@ fn get_dca_path() -> PathBuf {
     get_omnitrade_dir().join("dca.json")
 }
 
-fn load_dca_configs() -> Result<Vec<DCAConfig>, String> {
+pub(crate) fn load_dca_configs() -> Result<Vec<DCAConfig>, String> {
     let dca_path = get_dca_path();
     
     if !dca_path.exists() {
@@ -42,7 +113,7 @@ fn load_dca_configs() -> Result<Vec<DCAConfig>, String> {
     Ok(file.configs)
 }
 
-fn save_dca_configs(configs: &[DCAConfig]) -> Result<(), String> {
+pub(crate) fn save_dca_configs(configs: &[DCAConfig]) -> Result<(), String> {
     let dca_path = get_dca_path();
     let omnitrade_dir = get_omnitrade_dir();
     
@@ -80,3 +151,283 @@ pub async fn toggle_dca(id: String, enabled: bool) -> Result<(), String> {
     save_dca_configs(&configs)?;
     Ok(())
 }
+
+/// Value-averaging multiplier for a given drawdown from the recent high.
+///
+/// Scales linearly from 1× at no drawdown to `max_multiplier` once the
+/// drawdown reaches `full_scale_drawdown`, and is capped there.
+fn drawdown_multiplier(drawdown: f64, full_scale_drawdown: f64, max_multiplier: f64) -> f64 {
+    if full_scale_drawdown <= 0.0 {
+        return 1.0;
+    }
+    let scale = (drawdown / full_scale_drawdown).clamp(0.0, 1.0);
+    1.0 + scale * (max_multiplier - 1.0).max(0.0)
+}
+
+/// Suggest this cycle's DCA amount, buying more the further the asset is below its recent high.
+#[tauri::command]
+pub async fn suggest_dca_amount(asset: String, base_amount: f64) -> Result<f64, String> {
+    if base_amount <= 0.0 {
+        return Err("Base amount must be positive".to_string());
+    }
+    
+    let params = config::load_config()?.smart_dca.unwrap_or_default();
+    let lookback_days = params.lookback_days.unwrap_or(30).max(1);
+    let full_scale_drawdown = params.full_scale_drawdown_percent.unwrap_or(30.0) / 100.0;
+    let max_multiplier = params.max_multiplier.unwrap_or(2.0);
+    
+    let klines = prices::fetch_klines(&asset, "1d", None, lookback_days).await?;
+    let high = klines.iter().map(|k| k.high).fold(0.0, f64::max);
+    let current = klines.last().map(|k| k.close).ok_or("No price history for asset")?;
+    if high <= 0.0 {
+        return Ok(base_amount);
+    }
+    
+    let drawdown = (high - current) / high;
+    Ok(base_amount * drawdown_multiplier(drawdown, full_scale_drawdown, max_multiplier))
+}
+
+// Plan frequencies the daemon runs, shared with the CLI schema
+pub(crate) const FREQUENCIES: &[&str] = &["hourly", "daily", "weekly", "monthly"];
+
+fn executions_per_year(frequency: &str) -> Option<f64> {
+    match frequency {
+        "hourly" => Some(8760.0),
+        "daily" => Some(365.0),
+        "weekly" => Some(52.0),
+        "monthly" => Some(12.0),
+        _ => None,
+    }
+}
+
+/// Yearly contribution across enabled plans, per asset and in total.
+///
+/// Smart plans are excluded from the totals since their buy size varies; their
+/// base amount is reported separately as `smart_estimate`.
+#[tauri::command]
+pub async fn get_dca_annual_projection() -> Result<DcaProjection, String> {
+    let configs = load_dca_configs()?;
+    let mut per_asset: Vec<AssetProjection> = Vec::new();
+    let mut smart_estimate = 0.0;
+    
+    for config in configs.iter().filter(|c| c.enabled) {
+        let Some(per_year) = executions_per_year(&config.frequency) else {
+            continue;
+        };
+        let annual = config.amount * per_year;
+        
+        if config.smart {
+            smart_estimate += annual;
+            continue;
+        }
+        
+        match per_asset.iter_mut().find(|p| p.asset.eq_ignore_ascii_case(&config.asset)) {
+            Some(existing) => existing.annual_amount += annual,
+            None => per_asset.push(AssetProjection {
+                asset: config.asset.to_uppercase(),
+                annual_amount: annual,
+            }),
+        }
+    }
+    
+    per_asset.sort_by(|a, b| b.annual_amount.total_cmp(&a.annual_amount));
+    let total_annual = per_asset.iter().map(|p| p.annual_amount).sum();
+    let note = (smart_estimate > 0.0).then(|| {
+        "Smart DCA plans scale with drawdown; their base amount is estimated separately and may be exceeded".to_string()
+    });
+    
+    Ok(DcaProjection {
+        assets: per_asset,
+        total_annual,
+        smart_estimate,
+        note,
+    })
+}
+
+const HOUR_MS: i64 = 3_600_000;
+const DAY_MS: i64 = 24 * HOUR_MS;
+
+/// Buy times for a plan running from `from` to `to` at the given frequency.
+fn schedule_times(from: i64, to: i64, frequency: &str) -> Result<Vec<i64>, String> {
+    use chrono::{Months, TimeZone, Utc};
+    
+    let mut times = Vec::new();
+    match frequency {
+        "hourly" | "daily" | "weekly" => {
+            let step = match frequency {
+                "hourly" => HOUR_MS,
+                "daily" => DAY_MS,
+                _ => 7 * DAY_MS,
+            };
+            times.extend((0..).map(|i| from + i * step).take_while(|t| *t <= to));
+        }
+        "monthly" => {
+            let start = Utc.timestamp_millis_opt(from).single().ok_or("Invalid start time")?;
+            for i in 0.. {
+                let Some(t) = start.checked_add_months(Months::new(i)) else { break };
+                if t.timestamp_millis() > to {
+                    break;
+                }
+                times.push(t.timestamp_millis());
+            }
+        }
+        _ => return Err(format!("Unknown frequency: {} (expected hourly, daily, weekly or monthly)", frequency)),
+    }
+    Ok(times)
+}
+
+/// Replay buys of `amount_each` at the close of the candle covering each scheduled time,
+/// fees included.
+///
+/// With a slippage model, each fill is moved adversely by the buy's share of that candle's volume.
+fn backtest_buys(
+    klines: &[prices::Kline],
+    times: &[i64],
+    amount_each: f64,
+    fee_rate: f64,
+    to: i64,
+    slippage: Option<&SlippageConfig>,
+) -> BacktestResult {
+    let mut units = 0.0;
+    let mut invested = 0.0;
+    let mut buys = 0;
+    let mut slippage_cost = 0.0;
+    
+    for &t in times {
+        let Some(kline) = prices::kline_at(klines, t).filter(|k| k.close > 0.0) else {
+            continue;
+        };
+        let impact = slippage.map(|s| s.impact(amount_each, kline.volume * kline.close)).unwrap_or(0.0);
+        let price = kline.close * (1.0 + impact);
+        let bought = amount_each / (price * (1.0 + fee_rate));
+        units += bought;
+        invested += amount_each;
+        slippage_cost += bought * (price - kline.close);
+        buys += 1;
+    }
+    
+    let ending_value = units * prices::price_at(klines, to).unwrap_or(0.0);
+    let return_percent = if invested > 0.0 { (ending_value - invested) / invested * 100.0 } else { 0.0 };
+    let years = (to - times.first().copied().unwrap_or(to)) as f64 / (365.0 * DAY_MS as f64);
+    let annualized_return_percent = if years > 0.0 && invested > 0.0 {
+        ((ending_value / invested).powf(1.0 / years) - 1.0) * 100.0
+    } else {
+        return_percent
+    };
+    
+    BacktestResult {
+        invested,
+        units,
+        average_cost: if units > 0.0 { invested / units } else { 0.0 },
+        ending_value,
+        return_percent,
+        annualized_return_percent,
+        buys,
+        slippage_cost,
+    }
+}
+
+/// Backtest spreading `total_amount` over a DCA schedule against investing it all at `from`.
+///
+/// With `realistic`, fills pay volume-scaled slippage (see `slippage` in config) and the
+/// idealized close-price results are returned alongside for comparison.
+#[tauri::command]
+pub async fn compare_dca_vs_lumpsum(
+    asset: String,
+    total_amount: f64,
+    frequency: String,
+    from: i64,
+    to: i64,
+    realistic: Option<bool>,
+) -> Result<DcaComparison, String> {
+    if total_amount <= 0.0 {
+        return Err("Amount must be positive".to_string());
+    }
+    if to <= from {
+        return Err("`to` must be after `from`".to_string());
+    }
+    
+    let times = schedule_times(from, to, &frequency)?;
+    // Hourly plans need hourly candles, or every buy in a day would fill at the same close
+    let interval = if frequency == "hourly" { "1h" } else { "1d" };
+    let klines = prices::fetch_klines_between(&asset, interval, from, to).await?;
+    if klines.is_empty() {
+        return Err(format!("No price history for {} in range", asset));
+    }
+    
+    let cfg = config::load_config()?;
+    let fee_rate = cfg.fees.unwrap_or_default().taker();
+    let slippage = cfg.slippage.unwrap_or_default();
+    let realistic = realistic.unwrap_or(false);
+    let amount_each = total_amount / times.len() as f64;
+    
+    let run = |slippage: Option<&SlippageConfig>| {
+        (
+            backtest_buys(&klines, &times, amount_each, fee_rate, to, slippage),
+            backtest_buys(&klines, &[from], total_amount, fee_rate, to, slippage),
+        )
+    };
+    let (dca, lump_sum) = run(realistic.then_some(&slippage));
+    let (idealized_dca, idealized_lump_sum) = if realistic {
+        let (dca, lump_sum) = run(None);
+        (Some(dca), Some(lump_sum))
+    } else {
+        (None, None)
+    };
+    
+    Ok(DcaComparison {
+        asset: asset.to_uppercase(),
+        frequency,
+        from,
+        to,
+        dca,
+        lump_sum,
+        realistic,
+        idealized_dca,
+        idealized_lump_sum,
+    })
+}
+
+/// Backtest daily, weekly and monthly DCA over the last `lookback_days` with the same
+/// total investment and recommend whichever achieved the lowest average cost.
+#[tauri::command]
+pub async fn recommend_dca_frequency(asset: String, lookback_days: u32) -> Result<FrequencyRecommendation, String> {
+    if lookback_days < 30 {
+        return Err("Lookback must be at least 30 days to include a monthly buy".to_string());
+    }
+    
+    let to = chrono::Utc::now().timestamp_millis();
+    let from = to - lookback_days as i64 * DAY_MS;
+    let klines = prices::fetch_all_klines(&asset, "1d", from).await?;
+    if klines.is_empty() {
+        return Err(format!("No price history for {} in range", asset));
+    }
+    
+    // Any total works since average cost doesn't depend on it
+    let total_amount = 1000.0;
+    let fee_rate = config::load_config()?.fees.unwrap_or_default().taker();
+    let mut results = Vec::new();
+    for frequency in ["daily", "weekly", "monthly"] {
+        let times = schedule_times(from, to, frequency)?;
+        let result = backtest_buys(&klines, &times, total_amount / times.len() as f64, fee_rate, to, None);
+        results.push(FrequencyResult {
+            frequency: frequency.to_string(),
+            result,
+        });
+    }
+    
+    let recommended = results
+        .iter()
+        .filter(|r| r.result.units > 0.0)
+        .min_by(|a, b| a.result.average_cost.total_cmp(&b.result.average_cost))
+        .map(|r| r.frequency.clone())
+        .ok_or_else(|| format!("No buys could be simulated for {}", asset))?;
+    
+    Ok(FrequencyRecommendation {
+        asset: asset.to_uppercase(),
+        lookback_days,
+        results,
+        recommended,
+        note: "Based on the lowest average cost over this period only; past performance does not predict future results".to_string(),
+    })
+}
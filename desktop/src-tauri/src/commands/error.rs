@@ -0,0 +1,52 @@
+use serde::Serialize;
+use std::fmt;
+
+use super::exchanges::ClockSkewError;
+use super::notifications::WebhookError;
+use super::paper::{AllocationCapError, TradeLimitError};
+
+/// Error for commands whose failures the UI acts on.
+///
+/// A plain failure serializes as its message string, like every other command's
+/// `String` error; the structured variants serialize as objects with a `kind`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum CommandError {
+    Message(String),
+    ClockSkew(ClockSkewError),
+    Webhook(WebhookError),
+    AllocationCap(AllocationCapError),
+    TradeLimit(TradeLimitError),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            CommandError::Message(message) => message,
+            CommandError::ClockSkew(e) => &e.message,
+            CommandError::Webhook(e) => &e.message,
+            CommandError::AllocationCap(e) => &e.message,
+            CommandError::TradeLimit(e) => &e.message,
+        };
+        f.write_str(message)
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::Message(message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        CommandError::Message(message.to_string())
+    }
+}
+
+// Lets `?` hand a structured error to code that only deals in messages
+impl From<CommandError> for String {
+    fn from(error: CommandError) -> Self {
+        error.to_string()
+    }
+}
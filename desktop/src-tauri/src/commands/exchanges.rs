@@ -0,0 +1,763 @@
+use serde::{Deserialize, Serialize};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+use tokio::sync::Semaphore;
+
+use super::config;
+use super::error::CommandError;
+use crate::AppState;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeCapability {
+    pub id: String,
+    pub display_name: String,
+    pub supports_prices: bool,
+    pub supports_live_portfolio: bool,
+    pub supports_trading: bool,
+    pub supports_testnet: bool,
+}
+
+// Static capability table — update as exchange integrations land
+const EXCHANGES: &[(&str, &str, bool, bool, bool, bool)] = &[
+    // (id, display_name, prices, live_portfolio, trading, testnet)
+    ("binance", "Binance", true, true, false, true),
+    ("coinbase", "Coinbase", false, false, false, false),
+    ("kraken", "Kraken", false, false, false, false),
+];
+
+/// Whether `name` has a live account integration; every signed call goes to Binance,
+/// so keys for anything else must never be sent.
+pub(crate) fn supports_live_portfolio(name: &str) -> bool {
+    EXCHANGES
+        .iter()
+        .any(|&(id, _, _, live_portfolio, _, _)| id.eq_ignore_ascii_case(name) && live_portfolio)
+}
+
+#[tauri::command]
+pub async fn list_supported_exchanges() -> Result<Vec<ExchangeCapability>, String> {
+    Ok(EXCHANGES
+        .iter()
+        .map(|&(id, display_name, prices, live_portfolio, trading, testnet)| ExchangeCapability {
+            id: id.to_string(),
+            display_name: display_name.to_string(),
+            supports_prices: prices,
+            supports_live_portfolio: live_portfolio,
+            supports_trading: trading,
+            supports_testnet: testnet,
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClockSkewError {
+    pub kind: String,
+    pub offset_ms: i64,
+    pub message: String,
+}
+
+// Binance's default recvWindow; requests outside it are rejected
+pub const RECV_WINDOW_MS: i64 = 5000;
+// Keep a safety margin inside the window before treating skew as a problem
+const SKEW_MARGIN_MS: i64 = RECV_WINDOW_MS / 2;
+const SERVER_TIME_TTL_MS: i64 = 600_000;
+
+// `binanceApiBase` as last read from config.json; the outer None means not read yet
+static API_BASE_OVERRIDE: Mutex<Option<Option<String>>> = Mutex::new(None);
+
+/// Cache the `binanceApiBase` override from `cfg`. Called on every price tick and config save.
+pub(crate) fn refresh_api_base(cfg: &config::Config) {
+    if let Ok(mut cached) = API_BASE_OVERRIDE.lock() {
+        *cached = Some(cfg.binance_api_base.clone());
+    }
+}
+
+/// REST base URL for Binance. A configured `binanceApiBase` wins over the testnet switch.
+pub(crate) fn api_base(testnet: bool) -> String {
+    let cached = API_BASE_OVERRIDE.lock().ok().and_then(|cached| cached.clone());
+    let override_base = match cached {
+        Some(base) => base,
+        None => {
+            let cfg = config::load_config().unwrap_or_default();
+            refresh_api_base(&cfg);
+            cfg.binance_api_base
+        }
+    };
+    if let Some(base) = override_base {
+        return base;
+    }
+    if testnet {
+        "https://testnet.binance.vision".to_string()
+    } else {
+        "https://api.binance.com".to_string()
+    }
+}
+
+/// Server time minus local time in ms, cached for ten minutes.
+pub(crate) async fn server_time_offset(state: &AppState) -> Result<i64, String> {
+    let now = Utc::now().timestamp_millis();
+    let mut cached = state.server_time_offset.lock().await;
+    if let Some((fetched_at, offset)) = *cached {
+        if now - fetched_at < SERVER_TIME_TTL_MS {
+            return Ok(offset);
+        }
+    }
+    
+    let sent = Utc::now().timestamp_millis();
+    let response = reqwest::get(format!("{}/api/v3/time", api_base(false)))
+        .await
+        .map_err(|e| e.to_string())?;
+    record_used_weight(&response);
+    let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    let received = Utc::now().timestamp_millis();
+    
+    let server_time = body
+        .get("serverTime")
+        .and_then(|t| t.as_i64())
+        .ok_or("Invalid server time response")?;
+    // Assume the server stamped the response halfway through the round trip
+    let offset = server_time - (sent + received) / 2;
+    
+    *cached = Some((received, offset));
+    Ok(offset)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageFile {
+    last_used: HashMap<String, i64>,
+}
+
+const DEFAULT_SIGNED_CONCURRENCY: usize = 2;
+
+/// Per-exchange request semaphores with the limit each was built for.
+pub type SignedSemaphores = HashMap<String, (usize, Arc<Semaphore>)>;
+
+/// The exchange's request semaphore, rebuilt if the configured limit changed.
+async fn signed_semaphore(state: &AppState, exchange: &str, limit: usize) -> Arc<Semaphore> {
+    let mut semaphores = state.signed_semaphores.lock().await;
+    let entry = semaphores
+        .entry(exchange.to_lowercase())
+        .or_insert_with(|| (limit, Arc::new(Semaphore::new(limit))));
+    if entry.0 != limit {
+        *entry = (limit, Arc::new(Semaphore::new(limit)));
+    }
+    entry.1.clone()
+}
+
+fn get_usage_path() -> PathBuf {
+    let home = dirs::home_dir().expect("Could not find home directory");
+    home.join(".omnitrade").join("exchange-usage.json")
+}
+
+/// When each exchange last served a live call, keyed by lowercase name.
+fn load_usage() -> HashMap<String, i64> {
+    fs::read_to_string(get_usage_path())
+        .ok()
+        .and_then(|content| serde_json::from_str::<UsageFile>(&content).ok())
+        .map(|file| file.last_used)
+        .unwrap_or_default()
+}
+
+fn record_usage(exchange: &str) -> Result<(), String> {
+    let mut last_used = load_usage();
+    last_used.insert(exchange.to_lowercase(), Utc::now().timestamp_millis());
+    
+    let path = get_usage_path();
+    // Ensure directory exists
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(&UsageFile { last_used }).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| e.to_string())
+}
+
+// Hold new signed requests once this share of the per-minute weight is used
+const WEIGHT_HEADROOM: f64 = 0.9;
+
+// Binance's last reported 1-minute request weight: (reported at ms, weight). Weight is
+// counted per IP, so signed and unsigned responses both update it.
+static USED_WEIGHT: Mutex<(i64, u32)> = Mutex::new((0, 0));
+
+/// Record the `x-mbx-used-weight-1m` header of a Binance REST response.
+pub(crate) fn record_used_weight(response: &reqwest::Response) {
+    let used = response
+        .headers()
+        .get("x-mbx-used-weight-1m")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u32>().ok());
+    if let (Some(used), Ok(mut last)) = (used, USED_WEIGHT.lock()) {
+        *last = (Utc::now().timestamp_millis(), used);
+    }
+}
+
+/// Wait for the next minute if Binance's last reported weight usage is close to
+/// the REQUEST_WEIGHT limit. Binance's default limits apply until exchangeInfo is fetched.
+async fn wait_for_weight_budget(state: &AppState) {
+    let mut limits = state.rate_limits.lock().await.clone();
+    if limits.is_empty() {
+        limits = super::prices::default_rate_limits();
+    }
+    let per_minute = limits
+        .iter()
+        .find(|l| l.rate_limit_type == "REQUEST_WEIGHT" && l.interval == "MINUTE")
+        .map(|l| l.limit as f64 / l.interval_num.max(1) as f64);
+    let Some(per_minute) = per_minute else { return };
+    
+    let Ok((reported_at, used)) = USED_WEIGHT.lock().map(|last| *last) else { return };
+    let minute = reported_at.div_euclid(60_000);
+    let now = Utc::now().timestamp_millis();
+    if now.div_euclid(60_000) == minute && used as f64 >= per_minute * WEIGHT_HEADROOM {
+        let wait = (minute + 1) * 60_000 - now;
+        tokio::time::sleep(std::time::Duration::from_millis(wait.max(0) as u64)).await;
+    }
+}
+
+/// Make a signed GET request to a Binance endpoint with the stored credentials,
+/// recording the exchange as used on success.
+///
+/// Clock skew beyond the recvWindow margin is corrected automatically unless
+/// `autoClockSync` is disabled, in which case a `clockSkew` error is returned.
+pub(crate) async fn signed_get(
+    state: &AppState,
+    exchange: &str,
+    path: &str,
+    params: &[(&str, String)],
+) -> Result<serde_json::Value, CommandError> {
+    let body = send_signed_get(state, exchange, path, params).await?;
+    if let Err(e) = record_usage(exchange) {
+        eprintln!("Failed to record exchange usage: {}", e);
+    }
+    Ok(body)
+}
+
+async fn send_signed_get(
+    state: &AppState,
+    exchange: &str,
+    path: &str,
+    params: &[(&str, String)],
+) -> Result<serde_json::Value, CommandError> {
+    let cfg = config::load_config()?;
+    let creds = config::find_exchange(&cfg, exchange)
+        .ok_or_else(|| format!("Exchange not configured: {}", exchange))?;
+    send_signed_get_with(state, &cfg, exchange, creds, path, params).await
+}
+
+/// Signed GET with explicit credentials, which need not be saved in the config yet.
+async fn send_signed_get_with(
+    state: &AppState,
+    cfg: &config::Config,
+    exchange: &str,
+    creds: &config::ExchangeConfig,
+    path: &str,
+    params: &[(&str, String)],
+) -> Result<serde_json::Value, CommandError> {
+    let offset = server_time_offset(state).await?;
+    let mut applied_offset = 0;
+    if offset.abs() > SKEW_MARGIN_MS {
+        if !cfg.auto_clock_sync.unwrap_or(true) {
+            let error = ClockSkewError {
+                kind: "clockSkew".to_string(),
+                offset_ms: offset,
+                message: format!(
+                    "Your system clock is off by {:.1}s. Sync your clock and try again.",
+                    offset as f64 / 1000.0
+                ),
+            };
+            return Err(CommandError::ClockSkew(error));
+        }
+        applied_offset = offset;
+    }
+    
+    // Bound in-flight signed requests per exchange so startup bursts don't hit rate limits
+    let limit = cfg.signed_request_concurrency.unwrap_or(DEFAULT_SIGNED_CONCURRENCY).max(1);
+    let semaphore = signed_semaphore(state, exchange, limit).await;
+    let _permit = semaphore.acquire_owned().await.map_err(|e| e.to_string())?;
+    wait_for_weight_budget(state).await;
+    
+    // Serialize timestamp generation so concurrent requests get strictly increasing values
+    let timestamp = {
+        let mut last = state.last_signed_timestamp.lock().await;
+        let timestamp = (Utc::now().timestamp_millis() + applied_offset).max(*last + 1);
+        *last = timestamp;
+        timestamp
+    };
+    let mut query: Vec<String> = params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
+        .collect();
+    query.push(format!("recvWindow={}", RECV_WINDOW_MS));
+    query.push(format!("timestamp={}", timestamp));
+    let query = query.join("&");
+    
+    let mut mac = Hmac::<Sha256>::new_from_slice(creds.secret.as_bytes()).map_err(|e| e.to_string())?;
+    mac.update(query.as_bytes());
+    let signature = hex::encode(mac.finalize().into_bytes());
+    
+    let url = format!("{}{}?{}&signature={}", api_base(creds.testnet), path, query, signature);
+    let response = reqwest::Client::new()
+        .get(&url)
+        .header("X-MBX-APIKEY", &creds.api_key)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    record_used_weight(&response);
+    
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    if !status.is_success() {
+        let code = body.get("code").and_then(|c| c.as_i64()).unwrap_or(0);
+        let msg = body.get("msg").and_then(|m| m.as_str()).unwrap_or("unknown error");
+        return Err(match code {
+            -2014 | -2015 | -1022 => format!("Authentication failed: {}", msg),
+            _ => format!("Binance error {}: {}", code, msg),
+        }
+        .into());
+    }
+    
+    Ok(body)
+}
+
+/// Request to an endpoint that takes the API key but no signature, such as the
+/// user data stream's listenKey management.
+pub(crate) async fn api_key_request(
+    exchange: &str,
+    method: reqwest::Method,
+    path: &str,
+    params: &[(&str, String)],
+) -> Result<serde_json::Value, String> {
+    let cfg = config::load_config()?;
+    let creds = config::find_exchange(&cfg, exchange)
+        .ok_or_else(|| format!("Exchange not configured: {}", exchange))?;
+    
+    let response = reqwest::Client::new()
+        .request(method, format!("{}{}", api_base(creds.testnet), path))
+        .header("X-MBX-APIKEY", &creds.api_key)
+        .query(params)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    record_used_weight(&response);
+    
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    if !status.is_success() {
+        let code = body.get("code").and_then(|c| c.as_i64()).unwrap_or(0);
+        let msg = body.get("msg").and_then(|m| m.as_str()).unwrap_or("unknown error");
+        return Err(format!("Binance error {}: {}", code, msg));
+    }
+    
+    Ok(body)
+}
+
+const ACCOUNT_TTL_MS: i64 = 10_000;
+
+/// Signed `/api/v3/account` response, cached briefly per exchange.
+pub(crate) async fn account_snapshot(state: &AppState, exchange: &str) -> Result<serde_json::Value, CommandError> {
+    let key = exchange.to_lowercase();
+    let now = Utc::now().timestamp_millis();
+    if let Some((fetched_at, account)) = state.account_cache.lock().await.get(&key) {
+        if now - fetched_at < ACCOUNT_TTL_MS {
+            return Ok(account.clone());
+        }
+    }
+    
+    let account = signed_get(state, exchange, "/api/v3/account", &[]).await?;
+    state.account_cache.lock().await.insert(key, (now, account.clone()));
+    Ok(account)
+}
+
+/// Free (not locked in open orders) balance of one asset on a live exchange.
+#[tauri::command]
+pub async fn get_free_balance(
+    state: State<'_, AppState>,
+    exchange: String,
+    asset: String,
+) -> Result<f64, CommandError> {
+    let account = account_snapshot(&state, &exchange).await?;
+    
+    Ok(account
+        .get("balances")
+        .and_then(|b| b.as_array())
+        .and_then(|balances| {
+            balances
+                .iter()
+                .find(|b| b.get("asset").and_then(|a| a.as_str()).is_some_and(|a| a.eq_ignore_ascii_case(&asset)))
+        })
+        .and_then(|b| b.get("free"))
+        .and_then(|f| f.as_str())
+        .and_then(|f| f.parse::<f64>().ok())
+        .unwrap_or(0.0))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeTier {
+    pub exchange: String,
+    pub maker: f64,
+    pub taker: f64,
+    /// "account" when read from the exchange, "config" when falling back to configured fees
+    pub source: String,
+}
+
+fn config_fee_tier(exchange: &str) -> FeeTier {
+    let fees = config::load_config().ok().and_then(|c| c.fees).unwrap_or_default();
+    FeeTier {
+        exchange: exchange.to_lowercase(),
+        maker: fees.maker(),
+        taker: fees.taker(),
+        source: "config".to_string(),
+    }
+}
+
+/// The account's actual commission rates, cached for the session.
+///
+/// Falls back to the configured fees when the account can't be read.
+pub(crate) async fn fee_tier(state: &AppState, exchange: &str) -> FeeTier {
+    let key = exchange.to_lowercase();
+    if let Some(tier) = state.fee_tiers.lock().await.get(&key) {
+        return tier.clone();
+    }
+    
+    let rates = account_snapshot(state, exchange).await.ok().and_then(|account| {
+        let rates = account.get("commissionRates")?;
+        let rate = |k: &str| rates.get(k)?.as_str()?.parse::<f64>().ok();
+        Some((rate("maker")?, rate("taker")?))
+    });
+    
+    match rates {
+        Some((maker, taker)) => {
+            let tier = FeeTier {
+                exchange: key.clone(),
+                maker,
+                taker,
+                source: "account".to_string(),
+            };
+            state.fee_tiers.lock().await.insert(key, tier.clone());
+            tier
+        }
+        // Not cached, so a later call can pick up the real rates
+        None => config_fee_tier(exchange),
+    }
+}
+
+#[tauri::command]
+pub async fn get_fee_tier(state: State<'_, AppState>, exchange: String) -> Result<FeeTier, String> {
+    Ok(fee_tier(&state, &exchange).await)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyPermissions {
+    pub exchange: String,
+    pub can_read: bool,
+    /// Spot trading enabled and any trading authority still valid
+    pub can_trade: bool,
+    pub can_withdraw: bool,
+    pub enable_spot: bool,
+    pub enable_margin: bool,
+    pub enable_futures: bool,
+    pub ip_restricted: bool,
+    /// When temporary trading authority lapses, if Binance set one
+    pub trading_authority_expires: Option<i64>,
+    /// Plain-language problems for the UI to show, e.g. "This key can't place orders"
+    pub warnings: Vec<String>,
+}
+
+fn parse_key_permissions(exchange: &str, body: &serde_json::Value) -> KeyPermissions {
+    let flag = |key: &str| body.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+    let trading_authority_expires = body
+        .get("tradingAuthorityExpirationTime")
+        .and_then(|v| v.as_i64())
+        .filter(|t| *t > 0);
+    let authority_expired = trading_authority_expires.is_some_and(|t| t <= Utc::now().timestamp_millis());
+    
+    let mut permissions = KeyPermissions {
+        exchange: exchange.to_lowercase(),
+        can_read: flag("enableReading"),
+        can_trade: flag("enableSpotAndMarginTrading") && !authority_expired,
+        can_withdraw: flag("enableWithdrawals"),
+        enable_spot: flag("enableSpotAndMarginTrading"),
+        enable_margin: flag("enableMargin"),
+        enable_futures: flag("enableFutures"),
+        ip_restricted: flag("ipRestrict"),
+        trading_authority_expires,
+        warnings: Vec::new(),
+    };
+    
+    if !permissions.can_trade {
+        permissions.warnings.push("This key can't place orders".to_string());
+    }
+    if permissions.can_withdraw {
+        permissions.warnings.push("This key can withdraw funds; trading doesn't need that".to_string());
+    }
+    if !permissions.ip_restricted {
+        permissions.warnings.push("This key isn't restricted to trusted IPs".to_string());
+    }
+    permissions
+}
+
+/// What the stored API key is allowed to do, from `/sapi/v1/account/apiRestrictions`.
+///
+/// Cached for the session.
+#[tauri::command]
+pub async fn get_key_permissions(state: State<'_, AppState>, exchange: String) -> Result<KeyPermissions, CommandError> {
+    let key = exchange.to_lowercase();
+    if let Some(permissions) = state.key_permissions.lock().await.get(&key) {
+        return Ok(permissions.clone());
+    }
+    
+    let body = signed_get(&state, &exchange, "/sapi/v1/account/apiRestrictions", &[])
+        .await
+        .map_err(|e| match e {
+            CommandError::Message(e) if e.starts_with("Authentication failed") => CommandError::Message(format!(
+                "Binance refused to report this key's permissions ({}). The key may lack \
+                 \"Enable Reading\", be blocked by its IP whitelist, or be a testnet key, \
+                 which has no access to this endpoint.",
+                e
+            )),
+            other => other,
+        })?;
+    
+    let permissions = parse_key_permissions(&exchange, &body);
+    state.key_permissions.lock().await.insert(key, permissions.clone());
+    Ok(permissions)
+}
+
+#[tauri::command]
+pub async fn get_clock_skew(state: State<'_, AppState>) -> Result<i64, String> {
+    server_time_offset(&state).await
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeStatus {
+    pub status: String, // "normal" or "maintenance"
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceSystemStatus {
+    status: u8,
+    msg: String,
+}
+
+const STATUS_TTL_MS: i64 = 60_000;
+
+/// Binance system status (no auth), cached for a minute.
+pub(crate) async fn exchange_status(state: &AppState) -> Result<ExchangeStatus, String> {
+    let now = Utc::now().timestamp_millis();
+    let mut cached = state.exchange_status.lock().await;
+    if let Some((fetched_at, status)) = cached.as_ref() {
+        if now - fetched_at < STATUS_TTL_MS {
+            return Ok(status.clone());
+        }
+    }
+    
+    let response = reqwest::get(format!("{}/sapi/v1/system/status", api_base(false)))
+        .await
+        .map_err(|e| e.to_string())?;
+    record_used_weight(&response);
+    if !response.status().is_success() {
+        return Err(format!("System status request failed: {}", response.status()));
+    }
+    let body: BinanceSystemStatus = response.json().await.map_err(|e| e.to_string())?;
+    
+    let status = ExchangeStatus {
+        // 0 = normal, 1 = system maintenance
+        status: if body.status == 0 { "normal" } else { "maintenance" }.to_string(),
+        message: body.msg,
+    };
+    *cached = Some((now, status.clone()));
+    Ok(status)
+}
+
+#[tauri::command]
+pub async fn get_exchange_status(state: State<'_, AppState>) -> Result<ExchangeStatus, String> {
+    exchange_status(&state).await
+}
+
+/// One entry of an `import_exchanges` file.
+#[derive(Debug, Clone, Deserialize)]
+struct ImportEntry {
+    name: String,
+    #[serde(alias = "apiKey")]
+    api_key: String,
+    secret: String,
+    /// Entries that don't say otherwise are treated as testnet keys
+    #[serde(default = "config::default_testnet")]
+    testnet: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResult {
+    /// Normalized name, or the entry's position when it has none
+    pub name: String,
+    /// Masked API key; secrets are never returned
+    pub api_key: Option<String>,
+    pub testnet: bool,
+    pub saved: bool,
+    pub verified: bool,
+    pub error: Option<String>,
+}
+
+impl ImportResult {
+    fn failed(name: String, api_key: Option<String>, testnet: bool, error: impl Into<String>) -> Self {
+        ImportResult {
+            name,
+            api_key,
+            testnet,
+            saved: false,
+            verified: false,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// Save exchange credentials from a JSON file of `{ name, api_key, secret, testnet }`
+/// entries, optionally checking each with a signed ping first.
+///
+/// Invalid or unverifiable entries are skipped and reported rather than aborting the import.
+#[tauri::command]
+pub async fn import_exchanges(state: State<'_, AppState>, path: String, verify: bool) -> Result<Vec<ImportResult>, String> {
+    let content = fs::read_to_string(&path).map_err(|e| format!("Could not read {}: {}", path, e))?;
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&content)
+        .map_err(|e| format!("Expected a JSON array of exchange entries: {}", e))?;
+    
+    let cfg = config::load_config()?;
+    let now = Utc::now().timestamp_millis();
+    let mut results = Vec::new();
+    let mut imported: HashMap<String, config::ExchangeConfig> = HashMap::new();
+    
+    for (index, raw) in entries.into_iter().enumerate() {
+        let entry: ImportEntry = match serde_json::from_value(raw) {
+            Ok(entry) => entry,
+            Err(e) => {
+                results.push(ImportResult::failed(format!("entry {}", index + 1), None, false, e.to_string()));
+                continue;
+            }
+        };
+        let (name, api_key, secret) = match config::validate_exchange_input(&entry.name, &entry.api_key, &entry.secret) {
+            Ok(valid) => valid,
+            Err(e) => {
+                let name = entry.name.trim().to_lowercase();
+                let name = if name.is_empty() { format!("entry {}", index + 1) } else { name };
+                results.push(ImportResult::failed(name, None, entry.testnet, e.to_string()));
+                continue;
+            }
+        };
+        let masked = Some(config::mask_key(&api_key));
+        if imported.contains_key(&name) {
+            results.push(ImportResult::failed(name, masked, entry.testnet, "Duplicate of an earlier entry"));
+            continue;
+        }
+        
+        let creds = config::ExchangeConfig {
+            api_key,
+            secret,
+            testnet: entry.testnet,
+            saved_at: Some(now),
+            extra: Default::default(),
+        };
+        if verify {
+            if !supports_live_portfolio(&name) {
+                results.push(ImportResult::failed(name.clone(), masked, entry.testnet, format!("Credentials for {} can't be verified", name)));
+                continue;
+            }
+            if let Err(e) = send_signed_get_with(&state, &cfg, &name, &creds, "/api/v3/account", &[]).await {
+                results.push(ImportResult::failed(name, masked, entry.testnet, e));
+                continue;
+            }
+        }
+        
+        results.push(ImportResult {
+            name: name.clone(),
+            api_key: masked,
+            testnet: entry.testnet,
+            saved: true,
+            verified: verify,
+            error: None,
+        });
+        imported.insert(name, creds);
+    }
+    
+    if !imported.is_empty() {
+        // Imports can overwrite existing keys in bulk
+        config::backup_config()?;
+        // Reload so config edits made while keys were being verified are kept
+        let mut cfg = config::load_config()?;
+        for (name, creds) in imported {
+            // Cached permissions, fee tiers and balances belong to the replaced key
+            state.key_permissions.lock().await.remove(&name);
+            state.fee_tiers.lock().await.remove(&name);
+            state.account_cache.lock().await.remove(&name);
+            config::upsert_exchange(&mut cfg, &name, creds);
+        }
+        config::save_config(&cfg)?;
+    }
+    
+    Ok(results)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeAudit {
+    pub name: String,
+    /// Masked API key; secrets are never returned
+    pub api_key: String,
+    pub testnet: bool,
+    /// False for exchanges without a live integration; their keys aren't checked
+    pub supported: bool,
+    pub valid: bool,
+    pub error: Option<String>,
+    pub can_trade: Option<bool>,
+    pub last_used: Option<i64>,
+    pub saved_at: Option<i64>,
+}
+
+/// Check every stored exchange's credentials with a signed ping and report
+/// trading permission and last use, to help spot stale or broken keys.
+#[tauri::command]
+pub async fn audit_exchanges(state: State<'_, AppState>) -> Result<Vec<ExchangeAudit>, String> {
+    let cfg = config::load_config()?;
+    let usage = load_usage();
+    let mut names: Vec<&String> = cfg.exchanges.keys().collect();
+    names.sort();
+    
+    let mut audits = Vec::new();
+    for name in names {
+        let creds = &cfg.exchanges[name];
+        let supported = supports_live_portfolio(name);
+        // Ping without recording usage, so the audit itself doesn't make keys look active
+        let (valid, error, can_trade) = if !supported {
+            (false, Some(format!("{} is not supported for live accounts", name)), None)
+        } else {
+            match send_signed_get(&state, name, "/api/v3/account", &[]).await {
+                Ok(account) => (true, None, account.get("canTrade").and_then(|c| c.as_bool())),
+                Err(e) => (false, Some(e.to_string()), None),
+            }
+        };
+        audits.push(ExchangeAudit {
+            name: name.clone(),
+            api_key: config::mask_key(&creds.api_key),
+            testnet: creds.testnet,
+            supported,
+            valid,
+            error,
+            can_trade,
+            last_used: usage.get(&name.to_lowercase()).copied(),
+            saved_at: creds.saved_at,
+        });
+    }
+    
+    Ok(audits)
+}
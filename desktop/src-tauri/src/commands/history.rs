@@ -0,0 +1,908 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use chrono::{Local, NaiveDate, TimeZone, Utc};
+
+use super::ledger;
+use super::mock;
+use super::portfolio;
+use super::prices;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioSnapshot {
+    pub timestamp: i64,
+    pub total_value: f64,
+    pub usdt: f64,
+    /// Set on manual snapshots taken with `snapshot_portfolio`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// USDT value per asset (USDT included); absent on snapshots taken before it was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holdings: Option<HashMap<String, f64>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct HistoryFile {
+    snapshots: Vec<PortfolioSnapshot>,
+}
+
+/// External money moving in or out of the paper wallet, as opposed to trading gains.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashFlow {
+    pub timestamp: i64,
+    pub kind: String, // "deposit", "withdrawal", "reset"
+    /// Signed USDT amount: positive flows in, negative flows out
+    pub amount: f64,
+    /// Portfolio value immediately before the flow
+    pub value_before: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct CashFlowFile {
+    flows: Vec<CashFlow>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Twr {
+    pub from: i64,
+    pub to: i64,
+    pub period_return: f64,
+    pub annualized_return: f64,
+    /// Cash flows the series was broken at
+    pub flows: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDelta {
+    pub label: String,
+    pub snapshot_time: i64,
+    pub snapshot_value: f64,
+    pub current_value: f64,
+    pub change: f64,
+    pub change_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashDrag {
+    pub benchmark: String,
+    pub from: i64,
+    pub to: i64,
+    pub benchmark_return: f64,
+    pub cash_return: f64,
+    pub average_idle_usdt: f64,
+    pub drag_usdt: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Drawdown {
+    pub max_drawdown_percent: f64,
+    pub peak_time: i64,
+    pub peak_value: f64,
+    pub trough_time: i64,
+    pub trough_value: f64,
+    /// When the value first regained the peak after the trough, if it has
+    pub recovered_at: Option<i64>,
+    pub recovery_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyPnl {
+    /// Start of the UTC day
+    pub date: i64,
+    pub value: f64,
+    pub change: f64,
+    /// Gains locked in by that day's sells (FIFO, net of fees)
+    pub realized: f64,
+    /// Mark-to-market change on open positions: `change - realized`
+    pub unrealized: f64,
+    /// No snapshot that day; the last known value was carried forward
+    pub carried_forward: bool,
+}
+
+/// End-of-day recap of the paper portfolio for one local calendar day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetChange {
+    pub asset: String,
+    /// "added", "exited" or "held"
+    pub status: String,
+    pub from_value: f64,
+    pub to_value: f64,
+    pub value_change: f64,
+    pub from_allocation_percent: f64,
+    pub to_allocation_percent: f64,
+    /// Change in percentage points
+    pub allocation_change: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompositionDiff {
+    /// Timestamps of the snapshots actually compared
+    pub from: i64,
+    pub to: i64,
+    pub from_value: f64,
+    pub to_value: f64,
+    /// Largest absolute value change first
+    pub assets: Vec<AssetChange>,
+    pub added: Vec<String>,
+    pub exited: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailySummary {
+    /// Local date, `YYYY-MM-DD`
+    pub date: String,
+    pub day_start: i64,
+    /// Last value before the day began, or the day's first snapshot
+    pub open_value: f64,
+    pub close_value: f64,
+    pub high: f64,
+    pub low: f64,
+    pub realized_pnl: f64,
+    pub trades: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct DailySummaryFile {
+    summaries: Vec<DailySummary>,
+}
+
+const SNAPSHOT_INTERVAL_SECS: u64 = 3600;
+// Retention applied automatically at startup
+const AUTO_PRUNE_KEEP_DAYS: u32 = 365;
+// Recent history keeps full resolution when downsampling
+const DOWNSAMPLE_AFTER_DAYS: i64 = 7;
+const DAY_MS: i64 = 86_400_000;
+
+fn get_omnitrade_dir() -> PathBuf {
+    let home = dirs::home_dir().expect("Could not find home directory");
+    home.join(".omnitrade")
+}
+
+fn get_history_path() -> PathBuf {
+    get_omnitrade_dir().join("paper-history.json")
+}
+
+pub(crate) fn load_history() -> Result<Vec<PortfolioSnapshot>, String> {
+    let history_path = get_history_path();
+    
+    if !history_path.exists() {
+        return Ok(vec![]);
+    }
+    
+    let content = fs::read_to_string(&history_path).map_err(|e| e.to_string())?;
+    let file: HistoryFile = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    
+    Ok(file.snapshots)
+}
+
+fn save_history(snapshots: &[PortfolioSnapshot]) -> Result<(), String> {
+    let mut snapshots = snapshots.to_vec();
+    snapshots.sort_by_key(|s| s.timestamp);
+    
+    let omnitrade_dir = get_omnitrade_dir();
+    
+    // Ensure directory exists
+    if !omnitrade_dir.exists() {
+        fs::create_dir_all(&omnitrade_dir).map_err(|e| e.to_string())?;
+    }
+    
+    let file = HistoryFile { snapshots };
+    
+    let content = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    fs::write(get_history_path(), content).map_err(|e| e.to_string())?;
+    
+    Ok(())
+}
+
+fn get_flows_path() -> PathBuf {
+    get_omnitrade_dir().join("paper-flows.json")
+}
+
+pub(crate) fn load_cash_flows() -> Result<Vec<CashFlow>, String> {
+    let flows_path = get_flows_path();
+    
+    if !flows_path.exists() {
+        return Ok(vec![]);
+    }
+    
+    let content = fs::read_to_string(&flows_path).map_err(|e| e.to_string())?;
+    let file: CashFlowFile = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    
+    Ok(file.flows)
+}
+
+/// Append a cash flow so performance metrics can separate funding from returns.
+pub(crate) fn record_cash_flow(kind: &str, amount: f64, value_before: f64) -> Result<CashFlow, String> {
+    let flow = CashFlow {
+        timestamp: Utc::now().timestamp_millis(),
+        kind: kind.to_string(),
+        amount,
+        value_before,
+    };
+    
+    let mut flows = load_cash_flows()?;
+    flows.push(flow.clone());
+    
+    let omnitrade_dir = get_omnitrade_dir();
+    
+    // Ensure directory exists
+    if !omnitrade_dir.exists() {
+        fs::create_dir_all(&omnitrade_dir).map_err(|e| e.to_string())?;
+    }
+    
+    let file = CashFlowFile { flows };
+    let content = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    fs::write(get_flows_path(), content).map_err(|e| e.to_string())?;
+    
+    Ok(flow)
+}
+
+fn get_summaries_path() -> PathBuf {
+    get_omnitrade_dir().join("daily-summaries.json")
+}
+
+fn load_daily_summaries() -> Result<Vec<DailySummary>, String> {
+    let summaries_path = get_summaries_path();
+    
+    if !summaries_path.exists() {
+        return Ok(vec![]);
+    }
+    
+    let content = fs::read_to_string(&summaries_path).map_err(|e| e.to_string())?;
+    let file: DailySummaryFile = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    
+    Ok(file.summaries)
+}
+
+fn save_daily_summaries(summaries: &[DailySummary]) -> Result<(), String> {
+    let omnitrade_dir = get_omnitrade_dir();
+    
+    // Ensure directory exists
+    if !omnitrade_dir.exists() {
+        fs::create_dir_all(&omnitrade_dir).map_err(|e| e.to_string())?;
+    }
+    
+    let file = DailySummaryFile {
+        summaries: summaries.to_vec(),
+    };
+    
+    let content = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    fs::write(get_summaries_path(), content).map_err(|e| e.to_string())?;
+    
+    Ok(())
+}
+
+/// Start of a local calendar day in ms.
+pub(crate) fn local_day_start(date: NaiveDate) -> Option<i64> {
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|d| d.timestamp_millis())
+}
+
+fn local_date(timestamp: i64) -> Option<NaiveDate> {
+    Local.timestamp_millis_opt(timestamp).single().map(|d| d.date_naive())
+}
+
+/// Write a summary for every finished local day not yet summarized.
+///
+/// Catches up on days missed while the app was closed; days without any
+/// snapshot are skipped.
+fn summarize_completed_days() -> Result<u32, String> {
+    let mut summaries = load_daily_summaries()?;
+    let mut snapshots = load_history()?;
+    snapshots.sort_by_key(|s| s.timestamp);
+    
+    let mut next_day = match summaries.last() {
+        Some(last) => NaiveDate::parse_from_str(&last.date, "%Y-%m-%d")
+            .map_err(|e| e.to_string())?
+            .succ_opt(),
+        None => snapshots.first().and_then(|s| local_date(s.timestamp)),
+    };
+    
+    let today = Local::now().date_naive();
+    let trades = ledger::load_trades(None)?;
+    let realized = ledger::fifo_realized_lots(&trades);
+    let mut added = 0;
+    
+    while let Some(date) = next_day.filter(|d| *d < today) {
+        next_day = date.succ_opt();
+        let (Some(start), Some(end)) = (local_day_start(date), next_day.and_then(local_day_start)) else {
+            continue;
+        };
+        
+        let intraday: Vec<&PortfolioSnapshot> = snapshots
+            .iter()
+            .filter(|s| s.timestamp >= start && s.timestamp < end)
+            .collect();
+        let (Some(first), Some(last)) = (intraday.first(), intraday.last()) else {
+            continue;
+        };
+        let open_value = snapshots
+            .iter()
+            .rev()
+            .find(|s| s.timestamp < start)
+            .map(|s| s.total_value)
+            .unwrap_or(first.total_value);
+        
+        summaries.push(DailySummary {
+            date: date.format("%Y-%m-%d").to_string(),
+            day_start: start,
+            open_value,
+            close_value: last.total_value,
+            high: intraday.iter().map(|s| s.total_value).fold(f64::MIN, f64::max),
+            low: intraday.iter().map(|s| s.total_value).fold(f64::MAX, f64::min),
+            realized_pnl: realized
+                .iter()
+                .filter(|l| l.disposed >= start && l.disposed < end)
+                .map(|l| l.gain)
+                .sum(),
+            trades: trades.iter().filter(|t| t.timestamp >= start && t.timestamp < end).count() as u32,
+        });
+        added += 1;
+    }
+    
+    if added > 0 {
+        save_daily_summaries(&summaries)?;
+    }
+    Ok(added)
+}
+
+async fn record_snapshot(label: Option<String>) -> Result<PortfolioSnapshot, String> {
+    mock::ensure_inactive("Recording snapshots")?;
+    let (wallet, prices, total_value) = portfolio::current_wallet_value().await?;
+    
+    let mut holdings: HashMap<String, f64> = HashMap::new();
+    if wallet.usdt > 0.0 {
+        holdings.insert("USDT".to_string(), wallet.usdt);
+    }
+    for h in wallet.holdings.values().filter(|h| h.amount > 0.0) {
+        let asset = h.asset.to_uppercase();
+        if let Some(price) = prices.get(&asset) {
+            *holdings.entry(asset).or_default() += h.amount * price;
+        }
+    }
+    
+    let snapshot = PortfolioSnapshot {
+        timestamp: Utc::now().timestamp_millis(),
+        total_value,
+        usdt: wallet.usdt,
+        label,
+        holdings: Some(holdings),
+    };
+    
+    let mut snapshots = load_history()?;
+    snapshots.push(snapshot.clone());
+    save_history(&snapshots)?;
+    
+    Ok(snapshot)
+}
+
+fn prune_history(keep_days: u32) -> Result<u32, String> {
+    let cutoff = Utc::now().timestamp_millis() - keep_days as i64 * DAY_MS;
+    let snapshots = load_history()?;
+    let before = snapshots.len();
+    
+    let kept: Vec<PortfolioSnapshot> = snapshots.into_iter().filter(|s| s.timestamp >= cutoff).collect();
+    let removed = (before - kept.len()) as u32;
+    if removed > 0 {
+        save_history(&kept)?;
+    }
+    Ok(removed)
+}
+
+/// Background task recording an hourly snapshot of the paper portfolio value
+/// and summarizing each finished day.
+pub async fn snapshot_loop() {
+    if let Err(e) = prune_history(AUTO_PRUNE_KEEP_DAYS) {
+        eprintln!("Failed to prune portfolio history: {}", e);
+    }
+    
+    loop {
+        // Fixture prices would pollute the value history
+        if !mock::is_active() {
+            if let Err(e) = record_snapshot(None).await {
+                eprintln!("Failed to record portfolio snapshot: {}", e);
+            }
+        }
+        // The first snapshot of a new day closes out the previous one
+        if let Err(e) = summarize_completed_days() {
+            eprintln!("Failed to write daily summary: {}", e);
+        }
+        
+        tokio::time::sleep(Duration::from_secs(SNAPSHOT_INTERVAL_SECS)).await;
+    }
+}
+
+/// Record a snapshot right now, optionally labeled to mark a baseline.
+#[tauri::command]
+pub async fn snapshot_portfolio(label: Option<String>) -> Result<PortfolioSnapshot, String> {
+    let label = label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+    record_snapshot(label).await
+}
+
+/// Portfolio value change since the most recent snapshot with `label`.
+#[tauri::command]
+pub async fn change_since_snapshot(label: String) -> Result<SnapshotDelta, String> {
+    let label = label.trim().to_string();
+    let snapshot = load_history()?
+        .into_iter()
+        .filter(|s| s.label.as_deref() == Some(label.as_str()))
+        .max_by_key(|s| s.timestamp)
+        .ok_or_else(|| format!("No snapshot labeled \"{}\"", label))?;
+    
+    let (_, _, current_value) = portfolio::current_wallet_value().await?;
+    let change = current_value - snapshot.total_value;
+    
+    Ok(SnapshotDelta {
+        label,
+        snapshot_time: snapshot.timestamp,
+        snapshot_value: snapshot.total_value,
+        current_value,
+        change,
+        change_percent: if snapshot.total_value > 0.0 { change / snapshot.total_value * 100.0 } else { 0.0 },
+    })
+}
+
+/// The snapshot taken at `time`, or the latest one before it.
+fn snapshot_at(snapshots: &[PortfolioSnapshot], time: i64) -> Result<&PortfolioSnapshot, String> {
+    snapshots
+        .iter()
+        .filter(|s| s.timestamp <= time)
+        .max_by_key(|s| s.timestamp)
+        .ok_or_else(|| format!("No snapshot at or before {}", time))
+}
+
+/// Per-asset value and allocation changes between two snapshots, by timestamp.
+///
+/// Each timestamp picks the snapshot taken then or the latest one before it.
+#[tauri::command]
+pub async fn compare_composition(from_snapshot: i64, to_snapshot: i64) -> Result<CompositionDiff, String> {
+    if to_snapshot <= from_snapshot {
+        return Err("`toSnapshot` must be after `fromSnapshot`".to_string());
+    }
+    
+    let snapshots = load_history()?;
+    let (from, to) = (snapshot_at(&snapshots, from_snapshot)?, snapshot_at(&snapshots, to_snapshot)?);
+    let composition = |s: &PortfolioSnapshot| {
+        s.holdings
+            .clone()
+            .ok_or_else(|| format!("Snapshot at {} predates holdings being recorded", s.timestamp))
+    };
+    let (before, after) = (composition(from)?, composition(to)?);
+    
+    let percent = |value: f64, total: f64| if total > 0.0 { value / total * 100.0 } else { 0.0 };
+    let mut assets_seen: Vec<&String> = before.keys().chain(after.keys()).collect();
+    assets_seen.sort();
+    assets_seen.dedup();
+    
+    let mut assets: Vec<AssetChange> = assets_seen
+        .into_iter()
+        .map(|asset| {
+            let from_value = before.get(asset).copied().unwrap_or(0.0);
+            let to_value = after.get(asset).copied().unwrap_or(0.0);
+            let status = match (before.contains_key(asset), after.contains_key(asset)) {
+                (false, _) => "added",
+                (_, false) => "exited",
+                _ => "held",
+            };
+            let from_allocation_percent = percent(from_value, from.total_value);
+            let to_allocation_percent = percent(to_value, to.total_value);
+            AssetChange {
+                asset: asset.clone(),
+                status: status.to_string(),
+                from_value,
+                to_value,
+                value_change: to_value - from_value,
+                from_allocation_percent,
+                to_allocation_percent,
+                allocation_change: to_allocation_percent - from_allocation_percent,
+            }
+        })
+        .collect();
+    assets.sort_by(|a, b| b.value_change.abs().total_cmp(&a.value_change.abs()));
+    
+    let with_status = |status: &str| -> Vec<String> {
+        assets.iter().filter(|a| a.status == status).map(|a| a.asset.clone()).collect()
+    };
+    let (added, exited) = (with_status("added"), with_status("exited"));
+    
+    Ok(CompositionDiff {
+        from: from.timestamp,
+        to: to.timestamp,
+        from_value: from.total_value,
+        to_value: to.total_value,
+        assets,
+        added,
+        exited,
+    })
+}
+
+/// Snapshots within [from, to], in timestamp order.
+pub(crate) fn snapshots_between(from: i64, to: i64) -> Result<Vec<PortfolioSnapshot>, String> {
+    let mut snapshots: Vec<PortfolioSnapshot> = load_history()?
+        .into_iter()
+        .filter(|s| s.timestamp >= from && s.timestamp <= to)
+        .collect();
+    snapshots.sort_by_key(|s| s.timestamp);
+    Ok(snapshots)
+}
+
+#[tauri::command]
+pub async fn get_cash_drag(benchmark: String, from: i64) -> Result<CashDrag, String> {
+    let to = Utc::now().timestamp_millis();
+    if from >= to {
+        return Err("'from' must be in the past".to_string());
+    }
+    
+    let snapshots = snapshots_between(from, to)?;
+    if snapshots.is_empty() {
+        return Err("No portfolio history in the requested window".to_string());
+    }
+    
+    // Hourly candles cover ~41 days in one request; fall back to daily beyond that
+    let interval = if to - from <= 1000 * 3_600_000 { "1h" } else { "1d" };
+    let klines = prices::fetch_klines(&benchmark, interval, Some(from), 1000).await?;
+    let start_price = prices::price_at(&klines, from).ok_or("No benchmark price data")?;
+    let end_price = prices::price_at(&klines, to).ok_or("No benchmark price data")?;
+    
+    // Each snapshot's idle USDT is assumed held until the next snapshot (or now)
+    let mut drag_usdt = 0.0;
+    let mut weighted_usdt = 0.0;
+    for (i, snapshot) in snapshots.iter().enumerate() {
+        let period_end = snapshots.get(i + 1).map(|s| s.timestamp).unwrap_or(to);
+        let p0 = prices::price_at(&klines, snapshot.timestamp).unwrap_or(start_price);
+        let p1 = prices::price_at(&klines, period_end).unwrap_or(end_price);
+        if p0 > 0.0 {
+            drag_usdt += snapshot.usdt * (p1 / p0 - 1.0);
+        }
+        weighted_usdt += snapshot.usdt * (period_end - snapshot.timestamp) as f64;
+    }
+    
+    let span = (to - snapshots[0].timestamp).max(1) as f64;
+    
+    Ok(CashDrag {
+        benchmark: prices::to_binance_symbol(&benchmark),
+        from,
+        to,
+        benchmark_return: if start_price > 0.0 { (end_price / start_price - 1.0) * 100.0 } else { 0.0 },
+        cash_return: 0.0,
+        average_idle_usdt: weighted_usdt / span,
+        drag_usdt,
+    })
+}
+
+#[tauri::command]
+pub async fn prune_portfolio_history(keep_days: u32) -> Result<u32, String> {
+    prune_history(keep_days)
+}
+
+/// Collapse snapshots older than a week into one averaged point per bucket.
+///
+/// `bucket` is "hour", "day" or "week". Labeled snapshots are kept as-is.
+/// Returns the number of points removed.
+#[tauri::command]
+pub async fn downsample_portfolio_history(bucket: String) -> Result<u32, String> {
+    let bucket_ms = match bucket.as_str() {
+        "hour" => 3_600_000,
+        "day" => DAY_MS,
+        "week" => 7 * DAY_MS,
+        _ => return Err(format!("Unknown bucket: {} (expected hour, day or week)", bucket)),
+    };
+    
+    let cutoff = Utc::now().timestamp_millis() - DOWNSAMPLE_AFTER_DAYS * DAY_MS;
+    let mut snapshots = load_history()?;
+    snapshots.sort_by_key(|s| s.timestamp);
+    let before = snapshots.len();
+    
+    let (old, recent): (Vec<_>, Vec<_>) = snapshots
+        .into_iter()
+        .partition(|s| s.timestamp < cutoff && s.label.is_none());
+    
+    let mut result: Vec<PortfolioSnapshot> = Vec::new();
+    let mut group: Vec<PortfolioSnapshot> = Vec::new();
+    for snapshot in old {
+        let key = snapshot.timestamp.div_euclid(bucket_ms);
+        let same_bucket = group.first().map(|g| g.timestamp.div_euclid(bucket_ms) == key);
+        if same_bucket == Some(false) {
+            result.push(average_snapshots(&group, bucket_ms));
+            group.clear();
+        }
+        group.push(snapshot);
+    }
+    if !group.is_empty() {
+        result.push(average_snapshots(&group, bucket_ms));
+    }
+    result.extend(recent);
+    
+    let removed = (before - result.len()) as u32;
+    if removed > 0 {
+        save_history(&result)?;
+    }
+    Ok(removed)
+}
+
+fn average_snapshots(group: &[PortfolioSnapshot], bucket_ms: i64) -> PortfolioSnapshot {
+    let n = group.len() as f64;
+    PortfolioSnapshot {
+        timestamp: group[0].timestamp.div_euclid(bucket_ms) * bucket_ms,
+        total_value: group.iter().map(|s| s.total_value).sum::<f64>() / n,
+        usdt: group.iter().map(|s| s.usdt).sum::<f64>() / n,
+        label: None,
+        // Composition is kept as of the bucket's last recorded point rather than averaged
+        holdings: group.iter().rev().find_map(|s| s.holdings.clone()),
+    }
+}
+
+/// Largest peak-to-trough decline over the snapshots, which must be sorted by time.
+fn compute_max_drawdown(snapshots: &[PortfolioSnapshot]) -> Drawdown {
+    let mut result = Drawdown::default();
+    let first = match snapshots.first() {
+        Some(first) => first,
+        None => return result,
+    };
+    
+    let mut peak = first;
+    for snapshot in snapshots {
+        if snapshot.total_value > peak.total_value {
+            peak = snapshot;
+        } else if peak.total_value > 0.0 {
+            let drawdown = (peak.total_value - snapshot.total_value) / peak.total_value * 100.0;
+            if drawdown > result.max_drawdown_percent {
+                result = Drawdown {
+                    max_drawdown_percent: drawdown,
+                    peak_time: peak.timestamp,
+                    peak_value: peak.total_value,
+                    trough_time: snapshot.timestamp,
+                    trough_value: snapshot.total_value,
+                    recovered_at: None,
+                    recovery_ms: None,
+                };
+            }
+        }
+    }
+    
+    if result.max_drawdown_percent > 0.0 {
+        result.recovered_at = snapshots
+            .iter()
+            .find(|s| s.timestamp > result.trough_time && s.total_value >= result.peak_value)
+            .map(|s| s.timestamp);
+        result.recovery_ms = result.recovered_at.map(|t| t - result.trough_time);
+    }
+    result
+}
+
+#[tauri::command]
+pub async fn get_max_drawdown(from: Option<i64>, to: Option<i64>) -> Result<Drawdown, String> {
+    let snapshots = snapshots_between(from.unwrap_or(i64::MIN), to.unwrap_or(i64::MAX))?;
+    Ok(compute_max_drawdown(&snapshots))
+}
+
+/// Daily portfolio value change in [from, to], split into realized and unrealized PnL.
+#[tauri::command]
+pub async fn get_pnl_series(from: i64, to: i64) -> Result<Vec<DailyPnl>, String> {
+    if to <= from {
+        return Err("`to` must be after `from`".to_string());
+    }
+    
+    let mut snapshots = load_history()?;
+    snapshots.sort_by_key(|s| s.timestamp);
+    
+    let mut realized_by_day: HashMap<i64, f64> = HashMap::new();
+    for lot in ledger::fifo_realized_lots(&ledger::load_trades(None)?) {
+        *realized_by_day.entry(lot.disposed.div_euclid(DAY_MS)).or_default() += lot.gain;
+    }
+    
+    // Baseline is the last value before the range, so the first day has a real change
+    let mut last_value = snapshots
+        .iter()
+        .rev()
+        .find(|s| s.timestamp < from)
+        .map(|s| s.total_value);
+    
+    let mut series = Vec::new();
+    for day in from.div_euclid(DAY_MS)..=to.div_euclid(DAY_MS) {
+        let day_close = snapshots
+            .iter()
+            .rev()
+            .find(|s| s.timestamp.div_euclid(DAY_MS) == day)
+            .map(|s| s.total_value);
+        
+        let (value, carried_forward) = match (day_close, last_value) {
+            (Some(value), _) => (value, false),
+            (None, Some(value)) => (value, true),
+            // Nothing recorded yet
+            (None, None) => continue,
+        };
+        
+        let change = last_value.map(|prev| value - prev).unwrap_or(0.0);
+        let realized = realized_by_day.get(&day).copied().unwrap_or(0.0);
+        series.push(DailyPnl {
+            date: day * DAY_MS,
+            value,
+            change,
+            realized,
+            unrealized: change - realized,
+            carried_forward,
+        });
+        last_value = Some(value);
+    }
+    
+    Ok(series)
+}
+
+/// Daily summaries for the last `days` local days, oldest first.
+#[tauri::command]
+pub async fn get_daily_summaries(days: u32) -> Result<Vec<DailySummary>, String> {
+    let cutoff = Local::now().date_naive() - chrono::Days::new(days as u64);
+    let cutoff = cutoff.format("%Y-%m-%d").to_string();
+    
+    // ISO dates compare correctly as strings
+    Ok(load_daily_summaries()?
+        .into_iter()
+        .filter(|s| s.date >= cutoff)
+        .collect())
+}
+
+/// Time-weighted return over [from, to], chaining sub-period returns between cash flows.
+#[tauri::command]
+pub async fn get_twr(from: i64, to: i64) -> Result<Twr, String> {
+    if to <= from {
+        return Err("`to` must be after `from`".to_string());
+    }
+    
+    // Valuation points as (time, value before, value after); they differ only at flows
+    let mut points: Vec<(i64, f64, f64)> = snapshots_between(from, to)?
+        .into_iter()
+        .map(|s| (s.timestamp, s.total_value, s.total_value))
+        .collect();
+    let flows: Vec<CashFlow> = load_cash_flows()?
+        .into_iter()
+        .filter(|f| f.timestamp >= from && f.timestamp <= to)
+        .collect();
+    points.extend(flows.iter().map(|f| (f.timestamp, f.value_before, f.value_before + f.amount)));
+    points.sort_by_key(|p| p.0);
+    
+    if points.len() < 2 {
+        return Err("Not enough portfolio history in range".to_string());
+    }
+    
+    let mut growth = 1.0;
+    for pair in points.windows(2) {
+        let (start, end) = (pair[0].2, pair[1].1);
+        if start > 0.0 {
+            growth *= end / start;
+        }
+    }
+    
+    let years = (points[points.len() - 1].0 - points[0].0) as f64 / (365.0 * DAY_MS as f64);
+    let annualized_return = if years > 0.0 { growth.powf(1.0 / years) - 1.0 } else { 0.0 };
+    
+    Ok(Twr {
+        from,
+        to,
+        period_return: growth - 1.0,
+        annualized_return,
+        flows: flows.len() as u32,
+    })
+}
+
+/// Returns between consecutive snapshots in [from, to] as (return, period length in ms),
+/// with cash flows inside a period chained out as in `get_twr`.
+fn period_returns(from: i64, to: i64) -> Result<Vec<(f64, f64)>, String> {
+    // Valuation points as (time, value before, value after, is snapshot)
+    let mut points: Vec<(i64, f64, f64, bool)> = snapshots_between(from, to)?
+        .into_iter()
+        .map(|s| (s.timestamp, s.total_value, s.total_value, true))
+        .collect();
+    points.extend(
+        load_cash_flows()?
+            .into_iter()
+            .filter(|f| f.timestamp >= from && f.timestamp <= to)
+            .map(|f| (f.timestamp, f.value_before, f.value_before + f.amount, false)),
+    );
+    points.sort_by_key(|p| p.0);
+    
+    let mut returns = Vec::new();
+    let mut period_start: Option<i64> = None;
+    let mut growth = 1.0;
+    for pair in points.windows(2) {
+        let (start, end) = (pair[0].2, pair[1].1);
+        if start > 0.0 {
+            growth *= end / start;
+        }
+        period_start.get_or_insert(pair[0].0);
+        
+        if let (true, Some(started)) = (pair[1].3, period_start) {
+            let elapsed = (pair[1].0 - started) as f64;
+            if elapsed > 0.0 {
+                returns.push((growth - 1.0, elapsed));
+            }
+            period_start = None;
+            growth = 1.0;
+        }
+    }
+    
+    Ok(returns)
+}
+
+/// Annualized Sharpe ratio of portfolio returns over [from, to].
+///
+/// Returns are taken between consecutive snapshots, with cash flows chained out as in
+/// `get_twr`. `risk_free_rate` is annual (0.04 = 4%) and is scaled to each period's length.
+#[tauri::command]
+pub async fn get_sharpe_ratio(risk_free_rate: f64, from: i64, to: i64) -> Result<f64, String> {
+    if to <= from {
+        return Err("`to` must be after `from`".to_string());
+    }
+    
+    let year_ms = 365.0 * DAY_MS as f64;
+    let returns = period_returns(from, to)?;
+    if returns.len() < 2 {
+        return Err("Not enough portfolio history in range (need at least 3 snapshots)".to_string());
+    }
+    let excess_returns: Vec<f64> = returns
+        .iter()
+        .map(|(r, elapsed)| r - ((1.0 + risk_free_rate).powf(elapsed / year_ms) - 1.0))
+        .collect();
+    
+    let n = excess_returns.len() as f64;
+    let mean = excess_returns.iter().sum::<f64>() / n;
+    let variance = excess_returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    let std_dev = variance.sqrt();
+    if std_dev == 0.0 {
+        return Err("Returns have no variance; Sharpe ratio is undefined".to_string());
+    }
+    
+    // Snapshot spacing varies, so annualize by the average period length
+    let total_ms: f64 = returns.iter().map(|(_, elapsed)| elapsed).sum();
+    let periods_per_year = year_ms / (total_ms / n);
+    Ok(mean / std_dev * periods_per_year.sqrt())
+}
+
+// Fewest returns for a tail estimate to mean anything
+const MIN_CVAR_RETURNS: usize = 20;
+
+/// Historical expected shortfall (CVaR) over [from, to] at `confidence` (e.g. 0.95).
+///
+/// VaR is the loss at the (1 - confidence) quantile of snapshot-to-snapshot returns;
+/// CVaR is the average loss at or beyond it. Returned as a positive percentage of
+/// portfolio value per snapshot period.
+#[tauri::command]
+pub async fn get_cvar(confidence: f64, from: i64, to: i64) -> Result<f64, String> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err("Confidence must be between 0 and 1 (exclusive)".to_string());
+    }
+    if to <= from {
+        return Err("`to` must be after `from`".to_string());
+    }
+    
+    let mut returns: Vec<f64> = period_returns(from, to)?.into_iter().map(|(r, _)| r).collect();
+    if returns.len() < MIN_CVAR_RETURNS {
+        return Err(format!(
+            "Not enough portfolio history in range: {} returns, need at least {}",
+            returns.len(),
+            MIN_CVAR_RETURNS
+        ));
+    }
+    returns.sort_by(|a, b| a.total_cmp(b));
+    
+    // Worst returns up to and including the VaR cutoff
+    let tail_len = (((1.0 - confidence) * returns.len() as f64).ceil() as usize).max(1);
+    let tail = &returns[..tail_len];
+    let expected_shortfall = tail.iter().sum::<f64>() / tail.len() as f64;
+    
+    Ok(-expected_shortfall * 100.0)
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use chrono::Utc;
+
+use super::ledger;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    pub id: String,
+    pub timestamp: i64,
+    pub symbol: String,
+    pub note: String,
+    pub tags: Vec<String>,
+    /// Paper ledger trade this entry refers to, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trade_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct JournalFile {
+    entries: Vec<JournalEntry>,
+}
+
+fn get_omnitrade_dir() -> PathBuf {
+    let home = dirs::home_dir().expect("Could not find home directory");
+    home.join(".omnitrade")
+}
+
+fn get_journal_path() -> PathBuf {
+    get_omnitrade_dir().join("trade-journal.json")
+}
+
+fn load_journal() -> Result<Vec<JournalEntry>, String> {
+    let path = get_journal_path();
+    
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let file: JournalFile = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    
+    Ok(file.entries)
+}
+
+fn save_journal(entries: &[JournalEntry]) -> Result<(), String> {
+    let omnitrade_dir = get_omnitrade_dir();
+    
+    // Ensure directory exists
+    if !omnitrade_dir.exists() {
+        fs::create_dir_all(&omnitrade_dir).map_err(|e| e.to_string())?;
+    }
+    
+    let file = JournalFile {
+        entries: entries.to_vec(),
+    };
+    
+    let content = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    fs::write(get_journal_path(), content).map_err(|e| e.to_string())?;
+    
+    Ok(())
+}
+
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !out.contains(&tag) {
+            out.push(tag);
+        }
+    }
+    out
+}
+
+#[tauri::command]
+pub async fn add_journal_entry(
+    symbol: String,
+    note: String,
+    tags: Vec<String>,
+    trade_id: Option<String>,
+) -> Result<JournalEntry, String> {
+    let symbol = symbol.trim().to_uppercase();
+    if symbol.is_empty() {
+        return Err("Symbol is required".to_string());
+    }
+    let note = note.trim().to_string();
+    if note.is_empty() {
+        return Err("Note cannot be empty".to_string());
+    }
+    
+    if let Some(id) = &trade_id {
+        if !ledger::load_trades(None)?.iter().any(|t| &t.id == id) {
+            return Err(format!("Trade not found in ledger: {}", id));
+        }
+    }
+    
+    let now = Utc::now().timestamp_millis();
+    let entry = JournalEntry {
+        id: format!("journal_{}", now),
+        timestamp: now,
+        symbol,
+        note,
+        tags: normalize_tags(tags),
+        trade_id,
+    };
+    
+    let mut entries = load_journal()?;
+    entries.push(entry.clone());
+    save_journal(&entries)?;
+    
+    Ok(entry)
+}
+
+/// Journal entries, newest first, optionally filtered by symbol and tag.
+#[tauri::command]
+pub async fn get_journal(
+    symbol: Option<String>,
+    tag: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<JournalEntry>, String> {
+    let tag = tag.map(|t| t.trim().to_lowercase());
+    let mut entries: Vec<JournalEntry> = load_journal()?
+        .into_iter()
+        .filter(|e| symbol.as_ref().map(|s| e.symbol.eq_ignore_ascii_case(s.trim())).unwrap_or(true))
+        .filter(|e| tag.as_ref().map(|t| e.tags.contains(t)).unwrap_or(true))
+        .collect();
+    
+    entries.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+    if let Some(limit) = limit {
+        entries.truncate(limit as usize);
+    }
+    
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn remove_journal_entry(id: String) -> Result<(), String> {
+    let mut entries = load_journal()?;
+    let before = entries.len();
+    entries.retain(|e| e.id != id);
+    
+    if entries.len() == before {
+        return Err(format!("Journal entry not found: {}", id));
+    }
+    
+    save_journal(&entries)
+}
@@ -0,0 +1,342 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use chrono::{Datelike, TimeZone, Utc};
+
+use super::config;
+use super::paper;
+use super::portfolio::{self, Trade};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostBasis {
+    pub asset: String,
+    pub method: String,
+    pub units: f64,
+    /// USDT spent on buys (incl. fees) minus USDT received from sells (net of fees)
+    pub total_invested: f64,
+    /// Cost basis of the units still held
+    pub remaining_cost: f64,
+    pub average_entry: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxLot {
+    pub asset: String,
+    pub buy_trade_id: String,
+    pub sell_trade_id: String,
+    pub acquired: i64,
+    pub disposed: i64,
+    pub units: f64,
+    /// Sale value of these units, net of the sell fee
+    pub proceeds: f64,
+    pub cost_basis: f64,
+    pub gain: f64,
+    pub holding_days: i64,
+}
+
+/// Realized PnL as recorded versus replayed under different fee rates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PnlComparison {
+    pub maker_rate: f64,
+    pub taker_rate: f64,
+    pub trades: u32,
+    pub original_fees: f64,
+    pub recomputed_fees: f64,
+    pub original_realized: f64,
+    pub recomputed_realized: f64,
+    /// Recomputed minus original realized PnL
+    pub difference: f64,
+}
+
+/// An open buy lot: units still unmatched, their per-unit cost (incl. fee), and when bought.
+#[derive(Debug, Clone)]
+pub(crate) struct Lot {
+    pub units: f64,
+    pub unit_cost: f64,
+    pub acquired: i64,
+    pub buy_trade_id: String,
+}
+
+/// A buy closed out by a sell of the same asset shortly after.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundTrip {
+    pub asset: String,
+    pub buy_trade_id: String,
+    pub sell_trade_id: String,
+    pub bought_at: i64,
+    pub sold_at: i64,
+    pub hold_secs: i64,
+    pub units: f64,
+    /// Per-unit cost and proceeds, fees included
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub realized_pnl: f64,
+}
+
+/// The paper trade ledger for one asset (or all assets), oldest first.
+pub(crate) fn load_trades(asset: Option<&str>) -> Result<Vec<Trade>, String> {
+    let wallet = portfolio::load_wallet()?;
+    let mut trades: Vec<Trade> = wallet
+        .trades
+        .into_iter()
+        .filter(|t| asset.map(|a| t.asset.eq_ignore_ascii_case(a)).unwrap_or(true))
+        .collect();
+    trades.sort_by_key(|t| t.timestamp);
+    Ok(trades)
+}
+
+/// Cost of a buy per unit, with the fee folded into the basis.
+pub(crate) fn buy_unit_cost(trade: &Trade) -> f64 {
+    if trade.amount > 0.0 {
+        (trade.usdt_value + trade.fee) / trade.amount
+    } else {
+        0.0
+    }
+}
+
+/// Consume `units` from the front of the lot queue, returning the matched slices.
+pub(crate) fn take_fifo(lots: &mut VecDeque<Lot>, mut units: f64) -> Vec<Lot> {
+    let mut matched = Vec::new();
+    while units > 1e-12 {
+        let Some(front) = lots.front_mut() else { break };
+        let take = front.units.min(units);
+        matched.push(Lot {
+            units: take,
+            ..front.clone()
+        });
+        front.units -= take;
+        units -= take;
+        if front.units <= 1e-12 {
+            lots.pop_front();
+        }
+    }
+    matched
+}
+
+/// One asset's ledger replayed under a cost basis method.
+pub(crate) struct Replay {
+    pub units: f64,
+    pub total_invested: f64,
+    pub remaining_cost: f64,
+    /// Net proceeds minus cost basis of every unit sold
+    pub realized: f64,
+    /// Cost basis of every unit sold
+    pub realized_cost: f64,
+}
+
+/// The configured cost basis method, "average" unless set.
+pub(crate) fn cost_basis_method() -> Result<String, String> {
+    let method = config::load_config()?
+        .cost_basis_method
+        .unwrap_or_else(|| "average".to_string());
+    if method != "fifo" && method != "average" {
+        return Err(format!("Unknown cost basis method: {}", method));
+    }
+    Ok(method)
+}
+
+/// Replay one asset's trades, oldest first, under `method` ("fifo" or "average").
+pub(crate) fn replay_trades(trades: &[Trade], method: &str) -> Replay {
+    let mut total_invested = 0.0;
+    let mut lots: VecDeque<Lot> = VecDeque::new();
+    let (mut units, mut cost) = (0.0, 0.0);
+    let (mut realized, mut realized_cost) = (0.0, 0.0);
+    
+    for trade in trades {
+        match trade.side.as_str() {
+            "buy" => {
+                total_invested += trade.usdt_value + trade.fee;
+                units += trade.amount;
+                cost += trade.usdt_value + trade.fee;
+                lots.push_back(Lot {
+                    units: trade.amount,
+                    unit_cost: buy_unit_cost(trade),
+                    acquired: trade.timestamp,
+                    buy_trade_id: trade.id.clone(),
+                });
+            }
+            "sell" => {
+                total_invested -= trade.usdt_value - trade.fee;
+                let net_per_unit = if trade.amount > 0.0 { (trade.usdt_value - trade.fee) / trade.amount } else { 0.0 };
+                if method == "fifo" {
+                    for lot in take_fifo(&mut lots, trade.amount) {
+                        realized += lot.units * (net_per_unit - lot.unit_cost);
+                        realized_cost += lot.units * lot.unit_cost;
+                    }
+                } else if units > 0.0 {
+                    // Average cost: sells remove cost at the running average
+                    let sold = trade.amount.min(units);
+                    let sold_cost = cost / units * sold;
+                    cost -= sold_cost;
+                    realized += sold * net_per_unit - sold_cost;
+                    realized_cost += sold_cost;
+                }
+                units = (units - trade.amount).max(0.0);
+            }
+            _ => {}
+        }
+    }
+    
+    let remaining_cost = if method == "fifo" {
+        lots.iter().map(|l| l.units * l.unit_cost).sum()
+    } else {
+        cost.max(0.0)
+    };
+    
+    Replay {
+        units,
+        total_invested,
+        remaining_cost,
+        realized,
+        realized_cost,
+    }
+}
+
+fn compute_cost_basis(asset: &str, trades: &[Trade], method: &str) -> CostBasis {
+    let replay = replay_trades(trades, method);
+    CostBasis {
+        asset: asset.to_uppercase(),
+        method: method.to_string(),
+        units: replay.units,
+        total_invested: replay.total_invested,
+        remaining_cost: replay.remaining_cost,
+        average_entry: if replay.units > 0.0 { replay.remaining_cost / replay.units } else { 0.0 },
+    }
+}
+
+/// Match every sell against earlier buys of the same asset, oldest lot first.
+pub(crate) fn fifo_realized_lots(trades: &[Trade]) -> Vec<TaxLot> {
+    let mut open: HashMap<String, VecDeque<Lot>> = HashMap::new();
+    let mut realized = Vec::new();
+    
+    for trade in trades {
+        let asset = trade.asset.to_uppercase();
+        let lots = open.entry(asset.clone()).or_default();
+        match trade.side.as_str() {
+            "buy" => lots.push_back(Lot {
+                units: trade.amount,
+                unit_cost: buy_unit_cost(trade),
+                acquired: trade.timestamp,
+                buy_trade_id: trade.id.clone(),
+            }),
+            "sell" if trade.amount > 0.0 => {
+                let net_per_unit = (trade.usdt_value - trade.fee) / trade.amount;
+                for lot in take_fifo(lots, trade.amount) {
+                    let proceeds = lot.units * net_per_unit;
+                    let cost_basis = lot.units * lot.unit_cost;
+                    realized.push(TaxLot {
+                        asset: asset.clone(),
+                        buy_trade_id: lot.buy_trade_id,
+                        sell_trade_id: trade.id.clone(),
+                        acquired: lot.acquired,
+                        disposed: trade.timestamp,
+                        units: lot.units,
+                        proceeds,
+                        cost_basis,
+                        gain: proceeds - cost_basis,
+                        holding_days: (trade.timestamp - lot.acquired) / 86_400_000,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    
+    realized
+}
+
+/// Realized paper gains split into FIFO-matched lots, optionally for one asset and disposal year.
+#[tauri::command]
+pub async fn get_realized_lots(asset: Option<String>, year: Option<i32>) -> Result<Vec<TaxLot>, String> {
+    // Lots must be matched over the full history even when filtering by year
+    let trades = load_trades(asset.as_deref())?;
+    
+    Ok(fifo_realized_lots(&trades)
+        .into_iter()
+        .filter(|lot| match year {
+            Some(year) => Utc
+                .timestamp_millis_opt(lot.disposed)
+                .single()
+                .map(|t| t.year() == year)
+                .unwrap_or(false),
+            None => true,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_asset_cost_basis(asset: String) -> Result<CostBasis, String> {
+    let method = cost_basis_method()?;
+    let trades = load_trades(Some(&asset))?;
+    Ok(compute_cost_basis(&asset, &trades, &method))
+}
+
+/// Buys sold again within `max_hold_secs`, FIFO-matched, most recent first.
+///
+/// A sell spanning several buys yields one round trip per matched buy.
+#[tauri::command]
+pub async fn find_rapid_round_trips(max_hold_secs: u64) -> Result<Vec<RoundTrip>, String> {
+    let max_hold_ms = (max_hold_secs as i64).saturating_mul(1000);
+    let mut trips: Vec<RoundTrip> = fifo_realized_lots(&load_trades(None)?)
+        .into_iter()
+        .filter(|lot| lot.units > 0.0 && lot.disposed - lot.acquired <= max_hold_ms)
+        .map(|lot| RoundTrip {
+            hold_secs: (lot.disposed - lot.acquired) / 1000,
+            entry_price: lot.cost_basis / lot.units,
+            exit_price: lot.proceeds / lot.units,
+            asset: lot.asset,
+            buy_trade_id: lot.buy_trade_id,
+            sell_trade_id: lot.sell_trade_id,
+            bought_at: lot.acquired,
+            sold_at: lot.disposed,
+            units: lot.units,
+            realized_pnl: lot.gain,
+        })
+        .collect();
+    
+    trips.sort_by_key(|t| std::cmp::Reverse(t.sold_at));
+    Ok(trips)
+}
+
+/// Replay the ledger with fees of `maker_bps` / `taker_bps` basis points and compare
+/// realized PnL with what was recorded. Fills keep their units and prices; only fees
+/// change. Nothing is saved.
+#[tauri::command]
+pub async fn recompute_pnl_with_fees(maker_bps: f64, taker_bps: f64) -> Result<PnlComparison, String> {
+    if !(0.0..=1000.0).contains(&maker_bps) || !(0.0..=1000.0).contains(&taker_bps) {
+        return Err("Fee rates must be between 0 and 1000 basis points".to_string());
+    }
+    let (maker_rate, taker_rate) = (maker_bps / 10_000.0, taker_bps / 10_000.0);
+    
+    let trades = load_trades(None)?;
+    let replayed: Vec<Trade> = trades
+        .iter()
+        .map(|t| {
+            // Fills recorded before liquidity was tracked were all market orders
+            let rate = if t.liquidity.as_deref() == Some("maker") { maker_rate } else { taker_rate };
+            Trade {
+                fee: paper::trade_fee(t.usdt_value, rate),
+                fee_rate: Some(rate),
+                ..t.clone()
+            }
+        })
+        .collect();
+    
+    let realized = |trades: &[Trade]| fifo_realized_lots(trades).iter().map(|l| l.gain).sum::<f64>();
+    let original_realized = realized(&trades);
+    let recomputed_realized = realized(&replayed);
+    
+    Ok(PnlComparison {
+        maker_rate,
+        taker_rate,
+        trades: trades.len() as u32,
+        original_fees: trades.iter().map(|t| t.fee).sum(),
+        recomputed_fees: replayed.iter().map(|t| t.fee).sum(),
+        original_realized,
+        recomputed_realized,
+        difference: recomputed_realized - original_realized,
+    })
+}
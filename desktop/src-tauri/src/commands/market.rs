@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use chrono::Utc;
+use tauri::State;
+
+use crate::AppState;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FearGreed {
+    pub value: u8,
+    pub classification: String,
+    pub timestamp: i64,
+    /// True if the upstream was unreachable and this is the last cached value
+    pub stale: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct FngResponse {
+    data: Vec<FngEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FngEntry {
+    value: String,
+    value_classification: String,
+    timestamp: String,
+}
+
+// The index only updates daily, so an hour is plenty
+const FEAR_GREED_TTL_MS: i64 = 3_600_000;
+
+async fn fetch_fear_greed() -> Result<FearGreed, String> {
+    let response = reqwest::get("https://api.alternative.me/fng/")
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("Fear & Greed fetch failed: {}", response.status()));
+    }
+    
+    let body: FngResponse = response.json().await.map_err(|e| e.to_string())?;
+    let entry = body.data.into_iter().next().ok_or("Empty Fear & Greed response")?;
+    
+    Ok(FearGreed {
+        value: entry.value.parse().map_err(|_| "Invalid Fear & Greed value")?,
+        classification: entry.value_classification,
+        timestamp: entry.timestamp.parse::<i64>().unwrap_or(0) * 1000,
+        stale: false,
+    })
+}
+
+#[tauri::command]
+pub async fn get_fear_greed(state: State<'_, AppState>) -> Result<FearGreed, String> {
+    let now = Utc::now().timestamp_millis();
+    let mut cache = state.fear_greed_cache.lock().await;
+    
+    if let Some((fetched_at, cached)) = cache.as_ref() {
+        if now - fetched_at < FEAR_GREED_TTL_MS {
+            return Ok(cached.clone());
+        }
+    }
+    
+    match fetch_fear_greed().await {
+        Ok(fresh) => {
+            *cache = Some((now, fresh.clone()));
+            Ok(fresh)
+        }
+        // Upstream down: serve the last known value rather than failing
+        Err(e) => match cache.as_ref() {
+            Some((_, cached)) => Ok(FearGreed {
+                stale: true,
+                ..cached.clone()
+            }),
+            None => Err(e),
+        },
+    }
+}
@@ -0,0 +1,117 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Mutex;
+
+use super::config;
+use super::prices::{self, PriceData};
+
+// Ships with the app so demos work without preparing a file
+const BUNDLED_FIXTURE: &str = include_str!("../../fixtures/mock-prices.json");
+
+#[derive(Debug, Deserialize)]
+struct Fixture {
+    frames: Vec<Vec<PriceData>>,
+}
+
+struct MockFeed {
+    frames: Vec<Vec<PriceData>>,
+    position: usize,
+}
+
+/// Active synthetic price feed. Kept outside `AppState` because the price
+/// fetchers it replaces are called from places without access to state.
+static MOCK_FEED: Mutex<Option<MockFeed>> = Mutex::new(None);
+
+pub(crate) fn is_active() -> bool {
+    MOCK_FEED.lock().map(|feed| feed.is_some()).unwrap_or(false)
+}
+
+/// Refuse `action` while fixture prices are active, since its effects would be real.
+pub(crate) fn ensure_inactive(action: &str) -> Result<(), String> {
+    if is_active() {
+        return Err(format!("{} is disabled while mock prices are active", action));
+    }
+    Ok(())
+}
+
+/// Step to the next frame, wrapping at the end. Called once per price loop tick.
+pub(crate) fn advance() {
+    if let Ok(mut feed) = MOCK_FEED.lock() {
+        if let Some(feed) = feed.as_mut() {
+            feed.position = (feed.position + 1) % feed.frames.len();
+        }
+    }
+}
+
+/// Prices for the given symbols (any format) from the current frame.
+pub(crate) fn current_prices(symbols: &[String]) -> Vec<PriceData> {
+    let wanted: Vec<String> = symbols.iter().map(|s| s.replace('/', "").to_uppercase()).collect();
+    MOCK_FEED
+        .lock()
+        .ok()
+        .and_then(|feed| {
+            feed.as_ref().map(|feed| {
+                feed.frames[feed.position]
+                    .iter()
+                    .filter(|p| wanted.contains(&p.symbol.replace('/', "").to_uppercase()))
+                    .cloned()
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// USDT prices keyed by base asset from the current frame, like `fetch_price_map`.
+pub(crate) fn current_price_map() -> HashMap<String, f64> {
+    MOCK_FEED
+        .lock()
+        .ok()
+        .and_then(|feed| {
+            feed.as_ref().map(|feed| {
+                feed.frames[feed.position]
+                    .iter()
+                    .filter_map(|p| {
+                        let (base, quote) = prices::split_assets(&p.symbol).ok()?;
+                        (quote == "USDT").then_some((base, p.price))
+                    })
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Replay prices from a fixture instead of calling Binance, or switch back with `None`.
+///
+/// `path` is a JSON file shaped `{ "frames": [[PriceData, ...], ...] }`, or
+/// "bundled" for the fixture shipped with the app. Dev builds or `devMode` only.
+#[tauri::command]
+pub async fn set_mock_prices(path: Option<String>) -> Result<bool, String> {
+    let dev_mode = config::load_config()?.dev_mode.unwrap_or(false);
+    if !cfg!(debug_assertions) && !dev_mode {
+        return Err("set_mock_prices requires a debug build or devMode".to_string());
+    }
+    
+    let feed = match path {
+        None => None,
+        Some(path) => {
+            let content = if path == "bundled" {
+                BUNDLED_FIXTURE.to_string()
+            } else {
+                fs::read_to_string(&path).map_err(|e| e.to_string())?
+            };
+            let fixture: Fixture = serde_json::from_str(&content).map_err(|e| format!("Invalid fixture: {}", e))?;
+            if fixture.frames.is_empty() || fixture.frames.iter().any(|f| f.is_empty()) {
+                return Err("Fixture must contain at least one non-empty frame".to_string());
+            }
+            Some(MockFeed {
+                frames: fixture.frames,
+                position: 0,
+            })
+        }
+    };
+    
+    let active = feed.is_some();
+    *MOCK_FEED.lock().map_err(|e| e.to_string())? = feed;
+    Ok(active)
+}
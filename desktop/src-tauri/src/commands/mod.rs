@@ -2,5 +2,22 @@ pub mod alerts;
 pub mod config;
 pub mod daemon;
 pub mod dca;
+pub mod error;
+pub mod exchanges;
+pub mod history;
+pub mod journal;
+pub mod ledger;
+pub mod market;
+pub mod mock;
+pub mod notifications;
+pub mod paper;
 pub mod portfolio;
+pub mod predictions;
+pub mod preset;
 pub mod prices;
+pub mod recovery;
+pub mod reports;
+pub mod risk;
+pub mod scheduled;
+pub mod stream;
+pub mod watchlist;
@@ -0,0 +1,369 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use chrono::Utc;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::config::{self, NotificationConfig};
+use super::error::CommandError;
+use super::mock;
+use crate::AppState;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationEvent {
+    pub title: String,
+    pub message: String,
+    pub critical: bool,
+    /// True if DND suppressed delivery to at least one channel
+    pub suppressed: bool,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationResult {
+    pub channel: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookInfo {
+    /// "discord" or "slack"
+    pub provider: String,
+    pub name: Option<String>,
+    pub channel_id: Option<String>,
+    pub guild_id: Option<String>,
+}
+
+/// Why a webhook failed validation, returned as `CommandError::Webhook`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookError {
+    /// "malformed", "notFound" or "network"
+    pub kind: String,
+    pub message: String,
+}
+
+fn webhook_error(kind: &str, message: impl Into<String>) -> CommandError {
+    CommandError::Webhook(WebhookError {
+        kind: kind.to_string(),
+        message: message.into(),
+    })
+}
+
+/// A triggered alert waiting to be folded into its symbol's combined notification.
+#[derive(Debug, Clone)]
+pub struct QueuedAlert {
+    pub condition: String,
+    pub target: f64,
+    pub price: f64,
+}
+
+/// Alerts queued per symbol during the current coalescing window.
+pub type PendingAlerts = HashMap<String, (String, Vec<QueuedAlert>)>;
+
+const DEFAULT_COALESCE_WINDOW_SECS: u64 = 3;
+
+fn dnd_active(notifications: &NotificationConfig) -> bool {
+    notifications
+        .dnd_until
+        .map(|until| until > Utc::now().timestamp_millis())
+        .unwrap_or(false)
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "_*[]()~`>#+-=|{}.!\\".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+async fn send_telegram(bot_token: &str, chat_id: &str, title: &str, message: &str) -> Result<(), String> {
+    let body = serde_json::json!({
+        "chat_id": chat_id,
+        "text": format!("🔔 *{}*\n\n{}", escape_markdown(title), escape_markdown(message)),
+        "parse_mode": "MarkdownV2",
+    });
+    
+    let response = reqwest::Client::new()
+        .post(format!("https://api.telegram.org/bot{}/sendMessage", bot_token))
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Telegram returned {}", response.status()));
+    }
+    Ok(())
+}
+
+async fn send_discord(webhook_url: &str, title: &str, message: &str) -> Result<(), String> {
+    let body = serde_json::json!({
+        "embeds": [{
+            "title": format!("🔔 {}", title),
+            "description": message,
+            "color": 0x00d4aa, // OmniTrade teal
+            "timestamp": Utc::now().to_rfc3339(),
+            "footer": { "text": "OmniTrade by Connectry" },
+        }],
+    });
+    
+    let response = reqwest::Client::new()
+        .post(webhook_url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Discord returned {}", response.status()));
+    }
+    Ok(())
+}
+
+fn to_result(channel: &str, outcome: Result<(), String>) -> NotificationResult {
+    NotificationResult {
+        channel: channel.to_string(),
+        success: outcome.is_ok(),
+        error: outcome.err(),
+    }
+}
+
+/// Send a notification to all configured channels.
+///
+/// The frontend always receives a `notification` event. While DND is active,
+/// channel delivery is skipped unless the notification is critical and the
+/// channel opts in via `bypassDnd`. Nothing is delivered while mock prices are active.
+pub(crate) async fn dispatch(app: &AppHandle, title: &str, message: &str, critical: bool) -> Vec<NotificationResult> {
+    let notifications = config::load_config().ok().and_then(|c| c.notifications);
+    let dnd = notifications.as_ref().map(dnd_active).unwrap_or(false);
+    // Fixture prices must never reach a real phone or channel
+    let mocked = mock::is_active();
+    let allowed = |bypass: Option<bool>| !mocked && (!dnd || (critical && bypass.unwrap_or(false)));
+    
+    let mut results = Vec::new();
+    let mut suppressed = false;
+    
+    if let Some(n) = &notifications {
+        if let Some(tg) = n.telegram.as_ref().filter(|t| t.enabled) {
+            if let (Some(token), Some(chat_id)) = (&tg.bot_token, &tg.chat_id) {
+                if allowed(tg.bypass_dnd) {
+                    results.push(to_result("telegram", send_telegram(token, chat_id, title, message).await));
+                } else {
+                    suppressed = true;
+                }
+            }
+        }
+        if let Some(dc) = n.discord.as_ref().filter(|d| d.enabled) {
+            if let Some(url) = &dc.webhook_url {
+                if allowed(dc.bypass_dnd) {
+                    results.push(to_result("discord", send_discord(url, title, message).await));
+                } else {
+                    suppressed = true;
+                }
+            }
+        }
+    }
+    
+    if suppressed {
+        let reason = if mocked { "mock prices" } else { "DND" };
+        eprintln!("Notification suppressed by {}: {} — {}", reason, title, message);
+    }
+    
+    let _ = app.emit("notification", NotificationEvent {
+        title: title.to_string(),
+        message: message.to_string(),
+        critical,
+        suppressed,
+        timestamp: Utc::now().timestamp_millis(),
+    });
+    
+    results
+}
+
+/// Title and body for one symbol's batch of triggered alerts.
+fn alert_message(symbol: &str, batch: &[QueuedAlert]) -> (String, String) {
+    let last_price = batch.last().map(|a| a.price).unwrap_or(0.0);
+    if let [alert] = batch {
+        return (
+            format!("{} alert", symbol),
+            format!("{} is {} {} (now {})", symbol, alert.condition, alert.target, last_price),
+        );
+    }
+    
+    let mut parts = Vec::new();
+    for condition in ["above", "below"] {
+        let mut targets: Vec<f64> = batch.iter().filter(|a| a.condition == condition).map(|a| a.target).collect();
+        if targets.is_empty() {
+            continue;
+        }
+        targets.sort_by(f64::total_cmp);
+        if condition == "below" {
+            targets.reverse();
+        }
+        let targets: Vec<String> = targets.iter().map(|t| t.to_string()).collect();
+        parts.push(format!("{} {}", condition, targets.join(", ")));
+    }
+    
+    (
+        format!("{} alerts ({})", symbol, batch.len()),
+        format!("{} crossed {} (now {})", symbol, parts.join("; "), last_price),
+    )
+}
+
+/// Notify about a triggered alert.
+///
+/// Alerts on the same symbol that fire within `coalesceWindowSecs` (default 3s)
+/// are sent as a single combined message; a window of 0 sends each immediately.
+pub(crate) async fn dispatch_alert(app: &AppHandle, symbol: &str, alert: QueuedAlert) {
+    let window = config::load_config()
+        .ok()
+        .and_then(|c| c.notifications)
+        .and_then(|n| n.coalesce_window_secs)
+        .unwrap_or(DEFAULT_COALESCE_WINDOW_SECS);
+    
+    let state = match app.try_state::<AppState>() {
+        Some(state) if window > 0 => state,
+        _ => {
+            let (title, message) = alert_message(symbol, &[alert]);
+            dispatch(app, &title, &message, false).await;
+            return;
+        }
+    };
+    
+    let key = symbol.to_uppercase();
+    let opens_window = {
+        let mut pending = state.pending_alerts.lock().await;
+        let (_, batch) = pending.entry(key.clone()).or_insert_with(|| (symbol.to_string(), Vec::new()));
+        batch.push(alert);
+        batch.len() == 1
+    };
+    
+    // The first alert of a window schedules the flush; later ones just join the batch
+    if opens_window {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(window)).await;
+            let Some(state) = app.try_state::<AppState>() else { return };
+            let entry = state.pending_alerts.lock().await.remove(&key);
+            if let Some((symbol, batch)) = entry {
+                let (title, message) = alert_message(&symbol, &batch);
+                dispatch(&app, &title, &message, false).await;
+            }
+        });
+    }
+}
+
+/// Which provider a webhook URL belongs to, if it matches a known pattern.
+fn webhook_provider(url: &reqwest::Url) -> Option<&'static str> {
+    let host = url.host_str()?;
+    let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    
+    let discord_host = ["discord.com", "discordapp.com", "ptb.discord.com", "canary.discord.com"].contains(&host);
+    if discord_host {
+        if let ["api", "webhooks", id, _token] = segments.as_slice() {
+            if id.chars().all(|c| c.is_ascii_digit()) {
+                return Some("discord");
+            }
+        }
+    }
+    if host == "hooks.slack.com" && matches!(segments.as_slice(), ["services", _, _, _]) {
+        return Some("slack");
+    }
+    None
+}
+
+/// Check a Discord or Slack webhook URL without posting to it.
+///
+/// Discord webhooks return their metadata on GET; Slack ones can only be
+/// checked for existence.
+#[tauri::command]
+pub async fn validate_webhook(url: String) -> Result<WebhookInfo, CommandError> {
+    let parsed = reqwest::Url::parse(url.trim())
+        .map_err(|e| webhook_error("malformed", format!("Not a valid URL: {}", e)))?;
+    if parsed.scheme() != "https" {
+        return Err(webhook_error("malformed", "Webhook URL must use https"));
+    }
+    let provider = webhook_provider(&parsed)
+        .ok_or_else(|| webhook_error("malformed", "Not a Discord or Slack webhook URL"))?;
+    
+    let response = reqwest::Client::new()
+        .get(parsed)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| webhook_error("network", e.to_string()))?;
+    
+    let status = response.status();
+    if matches!(status.as_u16(), 401 | 403 | 404 | 410) {
+        return Err(webhook_error("notFound", format!("Webhook does not exist or was deleted ({})", status)));
+    }
+    if status.is_server_error() {
+        return Err(webhook_error("network", format!("{} returned {}", provider, status)));
+    }
+    
+    if provider == "slack" {
+        // Slack rejects the empty GET, but anything other than not-found means the hook exists
+        return Ok(WebhookInfo {
+            provider: provider.to_string(),
+            name: None,
+            channel_id: None,
+            guild_id: None,
+        });
+    }
+    
+    if !status.is_success() {
+        return Err(webhook_error("notFound", format!("Discord returned {}", status)));
+    }
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| webhook_error("network", e.to_string()))?;
+    let text = |key: &str| body.get(key).and_then(|v| v.as_str()).map(|v| v.to_string());
+    
+    Ok(WebhookInfo {
+        provider: provider.to_string(),
+        name: text("name"),
+        channel_id: text("channel_id"),
+        guild_id: text("guild_id"),
+    })
+}
+
+fn update_dnd(until: Option<i64>) -> Result<(), String> {
+    let mut config = config::load_config()?;
+    let notifications = config.notifications.get_or_insert(NotificationConfig {
+        native: None,
+        telegram: None,
+        discord: None,
+        dnd_until: None,
+        coalesce_window_secs: None,
+    });
+    notifications.dnd_until = until;
+    config::save_config(&config)
+}
+
+#[tauri::command]
+pub async fn set_dnd(until_ms: i64) -> Result<(), String> {
+    if until_ms <= Utc::now().timestamp_millis() {
+        return Err("DND end time must be in the future".to_string());
+    }
+    update_dnd(Some(until_ms))
+}
+
+#[tauri::command]
+pub async fn clear_dnd() -> Result<(), String> {
+    update_dnd(None)
+}
+
+#[tauri::command]
+pub async fn send_test_notification(app: AppHandle) -> Result<Vec<NotificationResult>, String> {
+    Ok(dispatch(&app, "Test notification", "OmniTrade notifications are working.", false).await)
+}
@@ -0,0 +1,731 @@
+use chrono::{Local, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use super::config;
+use super::error::CommandError;
+use super::history;
+use super::ledger;
+use super::mock;
+use super::portfolio::{self, Holding, PaperWallet, Trade};
+use super::prices;
+use crate::AppState;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletIssue {
+    /// "negativeBalance", "negativeAmount", "zeroAmount", "negativePrice", "costMismatch" or "notFinite"
+    pub kind: String,
+    pub asset: Option<String>,
+    pub message: String,
+    /// Whether `repair_paper_wallet` fixes it
+    pub repairable: bool,
+}
+
+impl WalletIssue {
+    fn new(kind: &str, asset: Option<&str>, message: String, repairable: bool) -> Self {
+        Self {
+            kind: kind.to_string(),
+            asset: asset.map(str::to_string),
+            message,
+            repairable,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AverageDownPreview {
+    pub asset: String,
+    pub price: f64,
+    pub units_bought: f64,
+    pub fee: f64,
+    pub current_amount: f64,
+    pub current_avg_price: f64,
+    pub new_amount: f64,
+    pub new_avg_price: f64,
+    /// Price at which selling the whole position recovers its cost, fees included
+    pub break_even_price: f64,
+    pub break_even_move_percent: f64,
+    pub new_position_value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdleYield {
+    /// Paper USDT balance the projection is based on
+    pub idle_usdt: f64,
+    pub apy_percent: f64,
+    pub daily: f64,
+    pub monthly: f64,
+    pub annual: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DustHolding {
+    pub asset: String,
+    pub amount: f64,
+    pub price: Option<f64>,
+    pub value: f64,
+    /// Smallest order the USDT pair accepts, if the pair exists
+    pub min_notional: Option<f64>,
+    /// Whether a market sell to USDT would pass the exchange filters
+    pub sellable: bool,
+    /// Why the holding can't be sold, when it can't
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DustConsolidation {
+    pub sold: Vec<Trade>,
+    pub usdt_received: f64,
+    /// Dust that stays in the wallet, e.g. below min-notional
+    pub stuck: Vec<DustHolding>,
+}
+
+/// The configured taker fee, paid by market orders and other immediate fills.
+pub(crate) fn taker_rate() -> Result<f64, String> {
+    Ok(config::load_config()?.fees.unwrap_or_default().taker())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationCapError {
+    pub kind: String,
+    pub asset: String,
+    pub cap_percent: f64,
+    pub projected_percent: f64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeLimitError {
+    pub kind: String,
+    pub limit: u32,
+    pub count: u32,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradesRemaining {
+    /// None when no daily limit is configured
+    pub limit: Option<u32>,
+    pub count: u32,
+    pub remaining: Option<u32>,
+    /// Start of the next local day, when the count resets
+    pub resets_at: Option<i64>,
+}
+
+/// Holdings worth less than this count as dust when no threshold is given.
+const DEFAULT_DUST_THRESHOLD_USDT: f64 = 10.0;
+
+pub(crate) fn trade_id() -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    format!("{}-{:x}", Utc::now().timestamp_millis(), nanos & 0xFFFFF)
+}
+
+/// Fee charged on a fill of `usdt_value`, taken in USDT.
+pub(crate) fn trade_fee(usdt_value: f64, fee_rate: f64) -> f64 {
+    usdt_value * fee_rate
+}
+
+/// Apply a fill to the wallet and append it to the ledger. Mirrors the CLI's
+/// executeBuy/executeSell so both front-ends produce identical wallets.
+///
+/// `liquidity` ("maker" or "taker") is recorded alongside the fee rate it was charged.
+pub(crate) fn apply_trade(
+    wallet: &mut PaperWallet,
+    side: &str,
+    asset: &str,
+    amount: f64,
+    price: f64,
+    fee_rate: f64,
+    liquidity: &str,
+) -> Result<Trade, String> {
+    if amount <= 0.0 {
+        return Err("Amount must be positive".to_string());
+    }
+    if price <= 0.0 {
+        return Err(format!("Invalid price for {}", asset));
+    }
+    
+    let asset = asset.to_uppercase();
+    let usdt_value = amount * price;
+    let fee = trade_fee(usdt_value, fee_rate);
+    
+    match side {
+        "buy" => {
+            let total_cost = usdt_value + fee;
+            if wallet.usdt < total_cost {
+                return Err(format!(
+                    "Insufficient USDT. Need ${:.2}, have ${:.2}",
+                    total_cost, wallet.usdt
+                ));
+            }
+            
+            let (prev_amount, prev_cost) = wallet
+                .holdings
+                .get(&asset)
+                .map(|h| (h.amount, h.total_cost))
+                .unwrap_or((0.0, 0.0));
+            let new_amount = prev_amount + amount;
+            let new_cost = prev_cost + usdt_value;
+            
+            wallet.usdt -= total_cost;
+            wallet.holdings.insert(asset.clone(), Holding {
+                asset: asset.clone(),
+                amount: new_amount,
+                avg_buy_price: new_cost / new_amount,
+                total_cost: new_cost,
+            });
+        }
+        "sell" => {
+            let holding = wallet
+                .holdings
+                .get(&asset)
+                .cloned()
+                .filter(|h| h.amount >= amount - 1e-10)
+                .ok_or_else(|| {
+                    let have = wallet.holdings.get(&asset).map(|h| h.amount).unwrap_or(0.0);
+                    format!("Insufficient {}. Need {}, have {:.8}", asset, amount, have)
+                })?;
+            
+            let new_amount = holding.amount - amount;
+            if new_amount < 1e-10 {
+                wallet.holdings.remove(&asset);
+            } else {
+                wallet.holdings.insert(asset.clone(), Holding {
+                    amount: new_amount,
+                    // Proportionally reduce cost basis
+                    total_cost: holding.avg_buy_price * new_amount,
+                    ..holding
+                });
+            }
+            wallet.usdt += usdt_value - fee;
+        }
+        _ => return Err(format!("Unknown side: {} (expected buy or sell)", side)),
+    }
+    
+    let trade = Trade {
+        id: trade_id(),
+        timestamp: Utc::now().timestamp_millis(),
+        side: side.to_string(),
+        asset: asset.clone(),
+        symbol: format!("{}/USDT", asset),
+        amount,
+        price,
+        usdt_value,
+        fee,
+        fee_asset: Some("USDT".to_string()),
+        balance_after: Some(wallet.usdt),
+        fee_rate: Some(fee_rate),
+        liquidity: Some(liquidity.to_string()),
+    };
+    wallet.trades.push(trade.clone());
+    Ok(trade)
+}
+
+fn usdt_reserve() -> Result<f64, String> {
+    Ok(config::load_config()?
+        .security
+        .and_then(|s| s.min_usdt_reserve)
+        .unwrap_or(0.0)
+        .max(0.0))
+}
+
+/// Refuse a spend that would dip into the configured USDT reserve.
+fn ensure_reserve(wallet: &PaperWallet, spend: f64) -> Result<(), String> {
+    let reserve = usdt_reserve()?;
+    if reserve > 0.0 && wallet.usdt - spend < reserve {
+        return Err(format!(
+            "Trade would breach the ${:.2} USDT reserve (available to trade: ${:.2})",
+            reserve,
+            (wallet.usdt - reserve).max(0.0)
+        ));
+    }
+    Ok(())
+}
+
+/// Refuse a buy of `amount` units at `price` that would take `asset` past its configured
+/// allocation cap.
+async fn ensure_allocation_cap(wallet: &PaperWallet, asset: &str, amount: f64, price: f64, fee_rate: f64) -> Result<(), CommandError> {
+    let Some(cap) = config::load_config()?.allocation_caps.and_then(|c| c.cap_for(asset)) else {
+        return Ok(());
+    };
+    
+    let asset = asset.to_uppercase();
+    let assets: Vec<String> = wallet.holdings.values().map(|h| h.asset.clone()).collect();
+    let mut prices = prices::fetch_price_map(&assets).await?;
+    prices.insert(asset.clone(), price);
+    
+    // The fee leaves the portfolio; the bought units move from USDT into the asset
+    let bought = amount * price;
+    let total_after = portfolio::wallet_value(wallet, &prices) - bought * fee_rate;
+    let held = wallet.holdings.get(&asset).map(|h| h.amount * price).unwrap_or(0.0);
+    let projected_percent = if total_after > 0.0 { (held + bought) / total_after * 100.0 } else { 100.0 };
+    
+    if projected_percent > cap {
+        let error = AllocationCapError {
+            kind: "allocationCap".to_string(),
+            asset: asset.clone(),
+            cap_percent: cap,
+            projected_percent,
+            message: format!(
+                "Buy would take {} to {:.1}% of the portfolio, above its {:.1}% cap",
+                asset, projected_percent, cap
+            ),
+        };
+        return Err(CommandError::AllocationCap(error));
+    }
+    Ok(())
+}
+
+/// Paper trades executed so far today (local calendar day), counted from the ledger.
+fn trades_today() -> Result<u32, String> {
+    let start = history::local_day_start(Local::now().date_naive()).ok_or("Could not determine the start of today")?;
+    Ok(ledger::load_trades(None)?.iter().filter(|t| t.timestamp >= start).count() as u32)
+}
+
+fn max_trades_per_day() -> Result<Option<u32>, String> {
+    Ok(config::load_config()?.security.and_then(|s| s.max_trades_per_day))
+}
+
+/// Refuse `new_trades` more trades if they'd take today's count past the configured
+/// daily limit.
+fn ensure_trade_limit(new_trades: u32) -> Result<(), CommandError> {
+    let Some(limit) = max_trades_per_day()? else {
+        return Ok(());
+    };
+    
+    let count = trades_today()?;
+    if count + new_trades > limit {
+        let error = TradeLimitError {
+            kind: "tradeLimit".to_string(),
+            limit,
+            count,
+            message: format!("Daily trade limit reached ({} of {} trades today)", count, limit),
+        };
+        return Err(CommandError::TradeLimit(error));
+    }
+    Ok(())
+}
+
+async fn current_price(asset: &str) -> Result<f64, String> {
+    prices::fetch_price_map(&[asset.to_string()])
+        .await?
+        .get(&asset.to_uppercase())
+        .copied()
+        .ok_or_else(|| format!("No USDT price for {}", asset))
+}
+
+/// Units a buy of `usdt_amount` gets at `price` once the fee is taken out.
+fn units_for_usdt(usdt_amount: f64, price: f64, fee_rate: f64) -> f64 {
+    usdt_amount / (price * (1.0 + fee_rate))
+}
+
+/// Buy `usdt_amount` worth of `asset` (fee included) at the current market price.
+pub(crate) async fn buy_with_usdt(asset: &str, usdt_amount: f64) -> Result<Trade, CommandError> {
+    mock::ensure_inactive("Paper trading")?;
+    ensure_trade_limit(1)?;
+    let price = current_price(asset).await?;
+    let fee_rate = taker_rate()?;
+    let amount = units_for_usdt(usdt_amount, price, fee_rate);
+    
+    let mut wallet = portfolio::load_wallet()?;
+    ensure_reserve(&wallet, usdt_amount)?;
+    ensure_allocation_cap(&wallet, asset, amount, price, fee_rate).await?;
+    let trade = apply_trade(&mut wallet, "buy", asset, amount, price, fee_rate, "taker")?;
+    portfolio::save_wallet(&wallet)?;
+    Ok(trade)
+}
+
+/// Execute a paper order for `amount` units of `asset`.
+///
+/// Market orders (the default) fill at the current price and pay the configured taker
+/// fee. A limit order is a resting order being filled: it needs `limit_price`, fills at
+/// that price once the market has reached it, and pays the configured maker fee.
+#[tauri::command]
+pub async fn execute_paper_trade(
+    side: String,
+    asset: String,
+    amount: f64,
+    order_type: Option<String>,
+    limit_price: Option<f64>,
+) -> Result<Trade, CommandError> {
+    mock::ensure_inactive("Paper trading")?;
+    ensure_trade_limit(1)?;
+    let market_price = current_price(&asset).await?;
+    let fees = config::load_config()?.fees.unwrap_or_default();
+    let (price, fee_rate, liquidity) = match order_type.as_deref().unwrap_or("market") {
+        "market" => (market_price, fees.taker(), "taker"),
+        "limit" => {
+            let limit = limit_price
+                .filter(|p| p.is_finite() && *p > 0.0)
+                .ok_or("Limit orders need a positive limitPrice")?;
+            let reached = match side.as_str() {
+                "buy" => market_price <= limit,
+                _ => market_price >= limit,
+            };
+            if !reached {
+                return Err(format!(
+                    "Limit {} not reached: {} is at {}",
+                    limit,
+                    asset.to_uppercase(),
+                    market_price
+                )
+                .into());
+            }
+            (limit, fees.maker(), "maker")
+        }
+        other => return Err(format!("Unknown order type: {} (expected market or limit)", other).into()),
+    };
+    
+    let mut wallet = portfolio::load_wallet()?;
+    if side == "buy" {
+        ensure_reserve(&wallet, amount * price * (1.0 + fee_rate))?;
+        ensure_allocation_cap(&wallet, &asset, amount, price, fee_rate).await?;
+    }
+    let trade = apply_trade(&mut wallet, &side, &asset, amount, price, fee_rate, liquidity)?;
+    portfolio::save_wallet(&wallet)?;
+    Ok(trade)
+}
+
+/// What adding `additional_usdt` to a holding at the current price would do to its
+/// average and break-even. Runs the same fill as `buy_with_usdt` on a copy of the
+/// wallet, so preview and execution agree; nothing is saved.
+#[tauri::command]
+pub async fn preview_average_down(asset: String, additional_usdt: f64) -> Result<AverageDownPreview, String> {
+    if additional_usdt <= 0.0 {
+        return Err("Amount must be positive".to_string());
+    }
+    let asset = asset.trim().to_uppercase();
+    let price = current_price(&asset).await?;
+    
+    let mut wallet = portfolio::load_wallet()?;
+    let (current_amount, current_avg_price) = wallet
+        .holdings
+        .get(&asset)
+        .map(|h| (h.amount, h.avg_buy_price))
+        .unwrap_or((0.0, 0.0));
+    
+    let fee_rate = taker_rate()?;
+    let amount = units_for_usdt(additional_usdt, price, fee_rate);
+    let trade = apply_trade(&mut wallet, "buy", &asset, amount, price, fee_rate, "taker")?;
+    let holding = wallet.holdings.get(&asset).ok_or("Preview produced no holding")?;
+    
+    // Selling everything at this price returns the cost basis plus both fees
+    let break_even_price = holding.total_cost * (1.0 + fee_rate) / (holding.amount * (1.0 - fee_rate));
+    
+    Ok(AverageDownPreview {
+        asset,
+        price,
+        units_bought: trade.amount,
+        fee: trade.fee,
+        current_amount,
+        current_avg_price,
+        new_amount: holding.amount,
+        new_avg_price: holding.avg_buy_price,
+        break_even_price,
+        break_even_move_percent: (break_even_price / price - 1.0) * 100.0,
+        new_position_value: holding.amount * price,
+    })
+}
+
+/// Trades left under the daily limit, for the UI.
+#[tauri::command]
+pub async fn get_trades_remaining_today() -> Result<TradesRemaining, String> {
+    let limit = max_trades_per_day()?;
+    let count = trades_today()?;
+    let tomorrow = Local::now().date_naive().succ_opt();
+    
+    Ok(TradesRemaining {
+        limit,
+        count,
+        remaining: limit.map(|l| l.saturating_sub(count)),
+        resets_at: tomorrow.and_then(history::local_day_start),
+    })
+}
+
+/// Paper USDT free to spend after the configured reserve, floored at zero.
+#[tauri::command]
+pub async fn get_available_to_trade() -> Result<f64, String> {
+    let wallet = portfolio::load_wallet()?;
+    Ok((wallet.usdt - usdt_reserve()?).max(0.0))
+}
+
+/// What the paper USDT balance would earn if staked at `apy` percent (5.0 = 5%).
+///
+/// APY already includes compounding, so shorter periods use the matching
+/// fractional power rather than a straight division. Informational only.
+#[tauri::command]
+pub async fn get_idle_yield_estimate(apy: f64) -> Result<IdleYield, String> {
+    if !apy.is_finite() || apy < 0.0 {
+        return Err("APY must be zero or positive".to_string());
+    }
+    
+    let idle_usdt = portfolio::load_wallet()?.usdt.max(0.0);
+    let growth = 1.0 + apy / 100.0;
+    
+    Ok(IdleYield {
+        idle_usdt,
+        apy_percent: apy,
+        daily: idle_usdt * (growth.powf(1.0 / 365.0) - 1.0),
+        monthly: idle_usdt * (growth.powf(1.0 / 12.0) - 1.0),
+        annual: idle_usdt * (growth - 1.0),
+    })
+}
+
+// Holdings below this are treated as empty, matching apply_trade
+const DUST: f64 = 1e-10;
+
+fn check_wallet(wallet: &PaperWallet) -> Vec<WalletIssue> {
+    let mut issues = Vec::new();
+    
+    if !wallet.usdt.is_finite() {
+        issues.push(WalletIssue::new("notFinite", None, format!("USDT balance is {}", wallet.usdt), false));
+    } else if wallet.usdt < 0.0 {
+        let message = format!("USDT balance is {:.2}", wallet.usdt);
+        issues.push(WalletIssue::new("negativeBalance", None, message, true));
+    }
+    
+    let mut assets: Vec<&String> = wallet.holdings.keys().collect();
+    assets.sort();
+    for key in assets {
+        let h = &wallet.holdings[key];
+        let asset = Some(key.as_str());
+        
+        if !h.amount.is_finite() || !h.avg_buy_price.is_finite() || !h.total_cost.is_finite() {
+            let message = format!("{} has a non-numeric amount or cost", key);
+            issues.push(WalletIssue::new("notFinite", asset, message, false));
+            continue;
+        }
+        if h.amount < -DUST {
+            let message = format!("{} amount is {}", key, h.amount);
+            issues.push(WalletIssue::new("negativeAmount", asset, message, true));
+        } else if h.amount < DUST {
+            issues.push(WalletIssue::new(
+                "zeroAmount",
+                asset,
+                format!("{} has no units but a cost of {:.2}", key, h.total_cost),
+                true,
+            ));
+        }
+        if h.avg_buy_price < 0.0 {
+            issues.push(WalletIssue::new(
+                "negativePrice",
+                asset,
+                format!("{} average buy price is {}", key, h.avg_buy_price),
+                true,
+            ));
+        }
+        let expected = h.amount.max(0.0) * h.avg_buy_price.max(0.0);
+        if h.amount >= DUST && (h.total_cost - expected).abs() > 1e-6 * expected.max(1.0) {
+            issues.push(WalletIssue::new(
+                "costMismatch",
+                asset,
+                format!("{} total cost {:.2} != amount × average ({:.2})", key, h.total_cost, expected),
+                true,
+            ));
+        }
+    }
+    
+    issues
+}
+
+/// Check the paper wallet for broken invariants without changing it.
+#[tauri::command]
+pub async fn validate_paper_wallet() -> Result<Vec<WalletIssue>, String> {
+    Ok(check_wallet(&portfolio::load_wallet()?))
+}
+
+/// Fix the safe invariant violations, backing the wallet up first.
+///
+/// Returns the issues that remain afterwards (those needing manual attention).
+#[tauri::command]
+pub async fn repair_paper_wallet() -> Result<Vec<WalletIssue>, String> {
+    let mut wallet = portfolio::load_wallet()?;
+    if !check_wallet(&wallet).iter().any(|i| i.repairable) {
+        return Ok(check_wallet(&wallet));
+    }
+    
+    portfolio::backup_wallet()?;
+    
+    if wallet.usdt < 0.0 {
+        wallet.usdt = 0.0;
+    }
+    wallet.holdings.retain(|_, h| !h.amount.is_finite() || h.amount >= DUST);
+    for h in wallet.holdings.values_mut() {
+        if !h.amount.is_finite() || !h.avg_buy_price.is_finite() || !h.total_cost.is_finite() {
+            continue;
+        }
+        h.avg_buy_price = h.avg_buy_price.max(0.0);
+        h.total_cost = h.amount * h.avg_buy_price;
+    }
+    
+    portfolio::save_wallet(&wallet)?;
+    Ok(check_wallet(&wallet))
+}
+
+/// Merge holdings of the same asset stored under differently cased keys,
+/// backing the wallet up first.
+#[tauri::command]
+pub async fn consolidate_holdings() -> Result<PaperWallet, String> {
+    let mut wallet = portfolio::load_wallet()?;
+    let fragmented = wallet.holdings.keys().any(|k| *k != k.to_uppercase());
+    if !fragmented {
+        return Ok(wallet);
+    }
+    
+    portfolio::backup_wallet()?;
+    portfolio::normalize_holdings(&mut wallet);
+    portfolio::save_wallet(&wallet)?;
+    
+    Ok(wallet)
+}
+
+/// Holdings worth less than `threshold_usdt`, smallest first, with whether each can be sold.
+async fn find_dust(state: &AppState, threshold_usdt: f64) -> Result<Vec<DustHolding>, String> {
+    if !threshold_usdt.is_finite() || threshold_usdt <= 0.0 {
+        return Err("Threshold must be positive".to_string());
+    }
+    
+    let (wallet, prices, _) = portfolio::current_wallet_value().await?;
+    let info = prices::exchange_info(state).await?;
+    let mut dust = Vec::new();
+    
+    for h in wallet.holdings.values().filter(|h| !h.asset.eq_ignore_ascii_case("USDT") && h.amount > 0.0) {
+        let asset = h.asset.to_uppercase();
+        let price = prices.get(&asset).copied();
+        let value = price.map(|p| h.amount * p).unwrap_or(0.0);
+        if price.is_some() && value >= threshold_usdt {
+            continue;
+        }
+        
+        let filters = info.get(&format!("{}USDT", asset));
+        let reason = match (price, filters) {
+            (None, _) | (_, None) => Some(format!("No {}/USDT market", asset)),
+            (_, Some(f)) if f.status != "TRADING" => Some(format!("{}/USDT is not trading ({})", asset, f.status)),
+            (_, Some(f)) if value < f.min_notional => {
+                Some(format!("Worth ${:.2}, below the ${:.2} min-notional", value, f.min_notional))
+            }
+            (_, Some(f)) if h.amount < f.min_qty => Some(format!("Below the {} min quantity", f.min_qty)),
+            _ => None,
+        };
+        
+        dust.push(DustHolding {
+            asset,
+            amount: h.amount,
+            price,
+            value,
+            min_notional: filters.map(|f| f.min_notional),
+            sellable: reason.is_none(),
+            reason,
+        });
+    }
+    
+    dust.sort_by(|a, b| a.value.total_cmp(&b.value));
+    Ok(dust)
+}
+
+/// Paper holdings worth less than `threshold_usdt`.
+#[tauri::command]
+pub async fn get_dust(state: State<'_, AppState>, threshold_usdt: f64) -> Result<Vec<DustHolding>, String> {
+    find_dust(&state, threshold_usdt).await
+}
+
+/// Market-sell every sellable dust holding to USDT, like Binance's dust conversion.
+///
+/// Backs the wallet up first. Dust that no order could clear is reported as stuck.
+#[tauri::command]
+pub async fn consolidate_dust(state: State<'_, AppState>, threshold_usdt: Option<f64>) -> Result<DustConsolidation, CommandError> {
+    mock::ensure_inactive("Paper trading")?;
+    let dust = find_dust(&state, threshold_usdt.unwrap_or(DEFAULT_DUST_THRESHOLD_USDT)).await?;
+    let (sellable, stuck): (Vec<DustHolding>, Vec<DustHolding>) = dust.into_iter().partition(|d| d.sellable);
+    if sellable.is_empty() {
+        return Ok(DustConsolidation {
+            sold: vec![],
+            usdt_received: 0.0,
+            stuck,
+        });
+    }
+    
+    // Each dust sale is a trade of its own
+    ensure_trade_limit(sellable.len() as u32)?;
+    portfolio::backup_wallet()?;
+    let fee_rate = taker_rate()?;
+    let mut wallet = portfolio::load_wallet()?;
+    let mut sold = Vec::new();
+    for d in &sellable {
+        let price = d.price.ok_or_else(|| format!("No USDT price for {}", d.asset))?;
+        sold.push(apply_trade(&mut wallet, "sell", &d.asset, d.amount, price, fee_rate, "taker")?);
+    }
+    portfolio::save_wallet(&wallet)?;
+    
+    Ok(DustConsolidation {
+        usdt_received: sold.iter().map(|t| t.usdt_value - t.fee).sum(),
+        sold,
+        stuck,
+    })
+}
+
+/// Replace the paper wallet with a fresh one, backing up the old wallet first.
+///
+/// Recorded as a cash flow so time-weighted returns don't count the reset as performance.
+#[tauri::command]
+pub async fn reset_paper_wallet() -> Result<PaperWallet, String> {
+    let (_, _, value_before) = portfolio::current_wallet_value().await?;
+    
+    portfolio::backup_wallet()?;
+    let wallet = portfolio::fresh_wallet();
+    portfolio::save_wallet(&wallet)?;
+    
+    history::record_cash_flow("reset", wallet.usdt - value_before, value_before)?;
+    Ok(wallet)
+}
+
+/// Add USDT to the paper wallet, recorded as a deposit rather than a gain.
+#[tauri::command]
+pub async fn deposit_usdt(amount: f64) -> Result<PaperWallet, String> {
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err("Amount must be positive".to_string());
+    }
+    
+    let (mut wallet, _, value_before) = portfolio::current_wallet_value().await?;
+    wallet.usdt += amount;
+    portfolio::save_wallet(&wallet)?;
+    
+    history::record_cash_flow("deposit", amount, value_before)?;
+    Ok(wallet)
+}
+
+/// Take USDT out of the paper wallet, never dipping into the configured reserve.
+#[tauri::command]
+pub async fn withdraw_usdt(amount: f64) -> Result<PaperWallet, String> {
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err("Amount must be positive".to_string());
+    }
+    
+    let (mut wallet, _, value_before) = portfolio::current_wallet_value().await?;
+    let available = (wallet.usdt - usdt_reserve()?).max(0.0);
+    if amount > available {
+        return Err(format!(
+            "Cannot withdraw ${:.2}: only ${:.2} available after the reserve",
+            amount, available
+        ));
+    }
+    
+    wallet.usdt -= amount;
+    portfolio::save_wallet(&wallet)?;
+    
+    history::record_cash_flow("withdrawal", -amount, value_before)?;
+    Ok(wallet)
+}
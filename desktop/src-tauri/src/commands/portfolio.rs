@@ -3,6 +3,16 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use tauri::State;
+
+use super::config;
+use super::error::CommandError;
+use super::exchanges;
+use super::ledger;
+use super::paper;
+use super::prices;
+use crate::AppState;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Holding {
@@ -12,6 +22,31 @@ pub struct Holding {
     pub total_cost: f64,
 }
 
+/// A paper fill, as recorded in the wallet's trade ledger.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Trade {
+    pub id: String,
+    pub timestamp: i64,
+    pub side: String, // "buy" or "sell"
+    pub asset: String,
+    pub symbol: String,
+    pub amount: f64,
+    pub price: f64,
+    pub usdt_value: f64,
+    pub fee: f64,
+    #[serde(default)]
+    pub fee_asset: Option<String>,
+    #[serde(default)]
+    pub balance_after: Option<f64>,
+    /// Fee rate charged on this fill
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_rate: Option<f64>,
+    /// "maker" for filled limit orders, "taker" for market orders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub liquidity: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PaperWallet {
@@ -19,6 +54,8 @@ pub struct PaperWallet {
     pub created_at: i64,
     pub usdt: f64,
     pub holdings: HashMap<String, Holding>,
+    #[serde(default)]
+    pub trades: Vec<Trade>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,23 +65,192 @@ pub struct PortfolioData {
     pub holdings: Vec<Holding>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioChange {
+    pub total_value: f64,
+    pub change_percent: f64,
+    pub change_usdt: f64,
+    /// Holdings without a 24h figure, left out of the weighting
+    pub excluded_assets: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitEstimate {
+    pub asset: String,
+    pub quantity: f64,
+    pub filled_quantity: f64,
+    pub proceeds: f64,
+    pub average_price: f64,
+    pub top_of_book: f64,
+    pub slippage_percent: f64,
+    /// True if the visible book can't absorb the whole holding
+    pub partial: bool,
+}
+
+/// One way to buy an asset: through a pair quoted in a currency the wallet holds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Route {
+    pub quote: String,
+    pub symbol: String,
+    /// Quote currency spent, converted from the requested USD amount
+    pub quote_amount: f64,
+    /// Units received after walking the book and paying the fee
+    pub quantity: f64,
+    pub fee: f64,
+    /// USD paid per unit received
+    pub effective_price_usd: f64,
+    pub spread_percent: f64,
+    /// Total premium over the pair's mid price: spread, depth and fee
+    pub cost_percent: f64,
+    pub held_balance: f64,
+    pub sufficient_balance: bool,
+    /// The book ran out before the full amount was spent
+    pub partial: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutePlan {
+    pub base: String,
+    pub usd_amount: f64,
+    pub best: Route,
+    /// Every route considered, most units received first
+    pub routes: Vec<Route>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HoldingPerf {
+    pub asset: String,
+    pub amount: f64,
+    pub avg_buy_price: f64,
+    pub price: Option<f64>,
+    pub value: f64,
+    pub pnl: f64,
+    pub pnl_percent: f64,
+    /// False when no market price could be fetched; such holdings sort last
+    pub priced: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioBreakEven {
+    /// Current value of priced non-USDT holdings
+    pub holdings_value: f64,
+    pub cost_basis: f64,
+    pub unrealized_pnl: f64,
+    /// Uniform move across all holdings that brings unrealized PnL to zero;
+    /// negative when in profit (the cushion before PnL turns negative)
+    pub required_move_percent: f64,
+    /// Basket value at break-even, i.e. the cost basis
+    pub target_value: f64,
+    pub in_profit: bool,
+    /// Holdings left out because no price was available
+    pub unpriced: Vec<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionSource {
+    /// "paper" or the exchange name
+    pub source: String,
+    pub units: f64,
+    pub value: Option<f64>,
+    /// None when the basis is unknown (live balances)
+    pub cost_basis: Option<f64>,
+    pub average_entry: Option<f64>,
+    /// Testnet balances are listed for reference but left out of the totals
+    pub testnet: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnifiedPosition {
+    pub asset: String,
+    pub price: Option<f64>,
+    pub total_units: f64,
+    pub total_value: Option<f64>,
+    pub known_basis_units: f64,
+    pub known_cost: f64,
+    /// Notional-weighted entry over the units whose basis is known
+    pub average_entry: Option<f64>,
+    pub unknown_basis_units: f64,
+    pub sources: Vec<PositionSource>,
+    /// Exchanges whose balance could not be fetched
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotalPnl {
+    /// Gains locked in by sells, under the configured cost basis method
+    pub realized: f64,
+    /// Paper gains on priced holdings still held
+    pub unrealized: f64,
+    pub total: f64,
+    /// Cost of all units sold plus the cost basis of priced units still held
+    pub total_invested: f64,
+    pub total_percent: f64,
+    /// Holdings left out because no price was available
+    pub unpriced: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BandResult {
+    /// Rebalance when any weight drifts this many percentage points from target
+    pub band_percent: f64,
+    pub rebalances: u32,
+    pub fees_paid: f64,
+    pub final_value: f64,
+    /// Return over the lookback after fees
+    pub net_return_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BandRecommendation {
+    pub lookback_days: u32,
+    pub results: Vec<BandResult>,
+    /// Band with the best fee-adjusted return
+    pub recommended_band_percent: f64,
+    /// Return had the starting allocation never been rebalanced
+    pub buy_and_hold_return_percent: f64,
+    pub note: String,
+}
+
 fn get_omnitrade_dir() -> PathBuf {
     let home = dirs::home_dir().expect("Could not find home directory");
     home.join(".omnitrade")
 }
 
-#[tauri::command]
-pub async fn get_paper_portfolio() -> Result<PaperWallet, String> {
-    let wallet_path = get_omnitrade_dir().join("paper-wallet.json");
+/// Starting balance of a new paper wallet, matching the CLI
+pub const INITIAL_USDT: f64 = 10_000.0;
+
+pub(crate) fn fresh_wallet() -> PaperWallet {
+    PaperWallet {
+        version: 1,
+        created_at: chrono::Utc::now().timestamp_millis(),
+        usdt: INITIAL_USDT,
+        holdings: HashMap::new(),
+        trades: vec![],
+    }
+}
+
+fn get_wallet_path() -> PathBuf {
+    get_omnitrade_dir().join("paper-wallet.json")
+}
+
+pub(crate) fn load_wallet() -> Result<PaperWallet, String> {
+    let wallet_path = get_wallet_path();
     
     if !wallet_path.exists() {
         // Return default wallet if not exists
-        return Ok(PaperWallet {
-            version: 1,
-            created_at: chrono::Utc::now().timestamp_millis(),
-            usdt: 10000.0,
-            holdings: HashMap::new(),
-        });
+        return Ok(fresh_wallet());
     }
     
     let content = fs::read_to_string(&wallet_path).map_err(|e| e.to_string())?;
@@ -53,12 +259,696 @@ pub async fn get_paper_portfolio() -> Result<PaperWallet, String> {
     Ok(wallet)
 }
 
+/// Key every holding by its uppercase asset, merging case-variant duplicates
+/// (`btc` and `BTC`) into one. Returns how many holdings were merged away.
+pub(crate) fn normalize_holdings(wallet: &mut PaperWallet) -> u32 {
+    let before = wallet.holdings.len();
+    let mut merged: HashMap<String, Holding> = HashMap::new();
+    
+    for (key, h) in wallet.holdings.drain() {
+        let asset = key.to_uppercase();
+        match merged.get_mut(&asset) {
+            Some(existing) => {
+                existing.amount += h.amount;
+                existing.total_cost += h.total_cost;
+                // Re-weight the average by units held in each fragment
+                if existing.amount > 0.0 {
+                    existing.avg_buy_price = existing.total_cost / existing.amount;
+                }
+            }
+            None => {
+                merged.insert(asset.clone(), Holding { asset, ..h });
+            }
+        }
+    }
+    
+    wallet.holdings = merged;
+    (before - wallet.holdings.len()) as u32
+}
+
+pub(crate) fn save_wallet(wallet: &PaperWallet) -> Result<(), String> {
+    let omnitrade_dir = get_omnitrade_dir();
+    
+    // Ensure directory exists
+    if !omnitrade_dir.exists() {
+        fs::create_dir_all(&omnitrade_dir).map_err(|e| e.to_string())?;
+    }
+    
+    let mut wallet = wallet.clone();
+    normalize_holdings(&mut wallet);
+    let content = serde_json::to_string_pretty(&wallet).map_err(|e| e.to_string())?;
+    fs::write(get_wallet_path(), content).map_err(|e| e.to_string())?;
+    
+    Ok(())
+}
+
+pub(crate) fn backup_wallet() -> Result<(), String> {
+    config::backup_file(&get_wallet_path())
+}
+
+/// Total wallet value in USDT. Holdings without a price are valued at zero.
+pub(crate) fn wallet_value(wallet: &PaperWallet, prices: &HashMap<String, f64>) -> f64 {
+    wallet.usdt
+        + wallet
+            .holdings
+            .values()
+            .map(|h| h.amount * prices.get(&h.asset.to_uppercase()).copied().unwrap_or(0.0))
+            .sum::<f64>()
+}
+
+/// Load the wallet and value it at current market prices.
+pub(crate) async fn current_wallet_value() -> Result<(PaperWallet, HashMap<String, f64>, f64), String> {
+    let wallet = load_wallet()?;
+    let assets: Vec<String> = wallet.holdings.values().map(|h| h.asset.clone()).collect();
+    let prices = prices::fetch_price_map(&assets).await?;
+    let total = wallet_value(&wallet, &prices);
+    Ok((wallet, prices, total))
+}
+
+#[tauri::command]
+pub async fn get_paper_portfolio() -> Result<PaperWallet, String> {
+    load_wallet()
+}
+
+/// USDT to buy (positive) or sell (negative) so `asset` makes up `target_percent` of the portfolio.
+///
+/// Uses the paper wallet's current value unless `total_portfolio` is given. Buys
+/// are grossed up for the trading fee so the position lands on target after fees.
+#[tauri::command]
+pub async fn amount_to_reach_allocation(
+    asset: String,
+    target_percent: f64,
+    total_portfolio: Option<f64>,
+) -> Result<f64, String> {
+    if !(0.0..=100.0).contains(&target_percent) {
+        return Err("Target percent must be between 0 and 100".to_string());
+    }
+    
+    let asset = asset.trim().to_uppercase();
+    let wallet = load_wallet()?;
+    let mut assets: Vec<String> = wallet.holdings.values().map(|h| h.asset.clone()).collect();
+    assets.push(asset.clone());
+    let prices = prices::fetch_price_map(&assets).await?;
+    
+    let price = prices
+        .get(&asset)
+        .copied()
+        .ok_or_else(|| format!("No USDT price for {}", asset))?;
+    let total = match total_portfolio {
+        Some(total) if total > 0.0 => total,
+        Some(_) => return Err("Total portfolio must be positive".to_string()),
+        None => wallet_value(&wallet, &prices),
+    };
+    
+    let current_value = wallet.holdings.get(&asset).map(|h| h.amount * price).unwrap_or(0.0);
+    let delta = total * target_percent / 100.0 - current_value;
+    
+    if delta > 0.0 {
+        Ok(delta * (1.0 + paper::taker_rate()?))
+    } else {
+        Ok(delta)
+    }
+}
+
+/// Drift bands tried by `recommend_rebalance_band`, in percentage points.
+const REBALANCE_BANDS: &[f64] = &[1.0, 3.0, 5.0, 10.0, 20.0];
+const DAY_MS: i64 = 86_400_000;
+
+/// Simulate daily drift checks over `closes` (one row of prices per day, in `weights`
+/// order), rebalancing to `weights` whenever one drifts past `band` percentage points.
+/// `band` of None never rebalances. Returns (final value, rebalances, fees) from 1.0.
+fn simulate_band(closes: &[Vec<f64>], weights: &[f64], band: Option<f64>, fee_rate: f64) -> (f64, u32, f64) {
+    let mut units: Vec<f64> = weights.iter().zip(&closes[0]).map(|(w, p)| w / p).collect();
+    let (mut rebalances, mut fees) = (0, 0.0);
+    
+    for day in &closes[1..] {
+        let values: Vec<f64> = units.iter().zip(day).map(|(u, p)| u * p).collect();
+        let total: f64 = values.iter().sum();
+        let Some(band) = band else { continue };
+        let drifted = values
+            .iter()
+            .zip(weights)
+            .any(|(v, w)| ((v / total - w) * 100.0).abs() > band);
+        if !drifted {
+            continue;
+        }
+        
+        // Every leg trades against USDT, so each side of the move pays the fee once
+        let traded: f64 = values.iter().zip(weights).map(|(v, w)| (total * w - v).abs()).sum();
+        let fee = traded * fee_rate;
+        let after_fees = total - fee;
+        units = weights.iter().zip(day).map(|(w, p)| after_fees * w / p).collect();
+        rebalances += 1;
+        fees += fee;
+    }
+    
+    let last = &closes[closes.len() - 1];
+    (units.iter().zip(last).map(|(u, p)| u * p).sum(), rebalances, fees)
+}
+
+/// Backtest rebalancing `targets` (asset → percent) at several drift bands over the last
+/// `lookback_days` of daily closes, and recommend the band with the best return after fees.
+#[tauri::command]
+pub async fn recommend_rebalance_band(
+    targets: HashMap<String, f64>,
+    lookback_days: u32,
+) -> Result<BandRecommendation, String> {
+    if targets.len() < 2 {
+        return Err("Need at least two target assets to rebalance between".to_string());
+    }
+    if targets.values().any(|p| !p.is_finite() || *p < 0.0) {
+        return Err("Target percents must be zero or positive".to_string());
+    }
+    let sum: f64 = targets.values().sum();
+    if (sum - 100.0).abs() > 0.01 {
+        return Err(format!("Target percents must add up to 100 (got {:.2})", sum));
+    }
+    if lookback_days < 7 {
+        return Err("Lookback must be at least 7 days".to_string());
+    }
+    
+    let mut assets: Vec<(String, f64)> = targets
+        .into_iter()
+        .map(|(asset, percent)| (asset.trim().to_uppercase(), percent / 100.0))
+        .collect();
+    assets.sort_by(|a, b| a.0.cmp(&b.0));
+    
+    let to = chrono::Utc::now().timestamp_millis();
+    let from = to - lookback_days as i64 * DAY_MS;
+    let mut klines = Vec::new();
+    for (asset, _) in &assets {
+        // Stablecoin targets act as cash at a flat $1
+        if STABLECOINS.contains(&asset.as_str()) {
+            klines.push(None);
+            continue;
+        }
+        let history = prices::fetch_all_klines(asset, "1d", from).await?;
+        if history.is_empty() {
+            return Err(format!("No price history for {} in range", asset));
+        }
+        klines.push(Some(history));
+    }
+    
+    // Start once every asset has history, so no leg is priced from a fallback candle
+    let start = klines
+        .iter()
+        .flatten()
+        .filter_map(|k| k.first().map(|k| k.open_time))
+        .max()
+        .unwrap_or(from);
+    let mut closes = Vec::new();
+    let mut day = start;
+    while day <= to {
+        let row: Option<Vec<f64>> = klines
+            .iter()
+            .map(|k| match k {
+                Some(k) => prices::price_at(k, day).filter(|p| *p > 0.0),
+                None => Some(1.0),
+            })
+            .collect();
+        closes.extend(row);
+        day += DAY_MS;
+    }
+    if closes.len() < 2 {
+        return Err("Not enough overlapping price history to backtest".to_string());
+    }
+    
+    let weights: Vec<f64> = assets.iter().map(|(_, w)| *w).collect();
+    let fee_rate = config::load_config()?.fees.unwrap_or_default().taker();
+    let results: Vec<BandResult> = REBALANCE_BANDS
+        .iter()
+        .map(|band| {
+            let (value, rebalances, fees) = simulate_band(&closes, &weights, Some(*band), fee_rate);
+            BandResult {
+                band_percent: *band,
+                rebalances,
+                fees_paid: fees * INITIAL_USDT,
+                final_value: value * INITIAL_USDT,
+                net_return_percent: (value - 1.0) * 100.0,
+            }
+        })
+        .collect();
+    let (hold_value, _, _) = simulate_band(&closes, &weights, None, fee_rate);
+    
+    let recommended_band_percent = results
+        .iter()
+        .max_by(|a, b| a.net_return_percent.total_cmp(&b.net_return_percent))
+        .map(|r| r.band_percent)
+        .unwrap_or(REBALANCE_BANDS[0]);
+    
+    Ok(BandRecommendation {
+        lookback_days,
+        results,
+        recommended_band_percent,
+        buy_and_hold_return_percent: (hold_value - 1.0) * 100.0,
+        note: format!(
+            "Simulated from ${:.0} with daily drift checks and {:.2}% taker fees; past performance does not predict future results",
+            INITIAL_USDT,
+            fee_rate * 100.0
+        ),
+    })
+}
+
+/// Unrealized PnL per paper holding, best performer first.
+#[tauri::command]
+pub async fn get_holding_performance() -> Result<Vec<HoldingPerf>, String> {
+    let (wallet, prices, _) = current_wallet_value().await?;
+    
+    let mut perf: Vec<HoldingPerf> = wallet
+        .holdings
+        .values()
+        .filter(|h| !h.asset.eq_ignore_ascii_case("USDT"))
+        .map(|h| {
+            let price = prices.get(&h.asset.to_uppercase()).copied();
+            let value = h.amount * price.unwrap_or(0.0);
+            let pnl = if price.is_some() { value - h.total_cost } else { 0.0 };
+            HoldingPerf {
+                asset: h.asset.to_uppercase(),
+                amount: h.amount,
+                avg_buy_price: h.avg_buy_price,
+                price,
+                value,
+                pnl,
+                pnl_percent: if h.total_cost > 0.0 { pnl / h.total_cost * 100.0 } else { 0.0 },
+                priced: price.is_some(),
+            }
+        })
+        .collect();
+    
+    perf.sort_by(|a, b| b.priced.cmp(&a.priced).then(b.pnl_percent.total_cmp(&a.pnl_percent)));
+    Ok(perf)
+}
+
+/// How far the whole basket of paper holdings must move, uniformly, for unrealized PnL to reach zero.
+#[tauri::command]
+pub async fn get_portfolio_break_even() -> Result<PortfolioBreakEven, String> {
+    let (wallet, prices, _) = current_wallet_value().await?;
+    let mut holdings_value = 0.0;
+    let mut cost_basis = 0.0;
+    let mut unpriced = Vec::new();
+    
+    for h in wallet.holdings.values().filter(|h| !h.asset.eq_ignore_ascii_case("USDT") && h.amount > 0.0) {
+        match prices.get(&h.asset.to_uppercase()) {
+            Some(price) => {
+                holdings_value += h.amount * price;
+                cost_basis += h.total_cost;
+            }
+            None => unpriced.push(h.asset.to_uppercase()),
+        }
+    }
+    unpriced.sort();
+    
+    let unrealized_pnl = holdings_value - cost_basis;
+    let (required_move_percent, note) = if holdings_value <= 0.0 {
+        (0.0, Some("No priced holdings; the portfolio is all cash".to_string()))
+    } else if unrealized_pnl >= 0.0 {
+        (
+            (cost_basis / holdings_value - 1.0) * 100.0,
+            Some("In profit: the move shown is the drop that would erase all gains".to_string()),
+        )
+    } else {
+        ((cost_basis / holdings_value - 1.0) * 100.0, None)
+    };
+    
+    Ok(PortfolioBreakEven {
+        holdings_value,
+        cost_basis,
+        unrealized_pnl,
+        required_move_percent,
+        target_value: cost_basis,
+        in_profit: holdings_value > 0.0 && unrealized_pnl >= 0.0,
+        unpriced,
+        note,
+    })
+}
+
+const STABLECOINS: &[&str] = &["USDT", "USDC", "BUSD", "FDUSD", "DAI", "TUSD"];
+
+/// Non-zero (free + locked) balances from a signed account response.
+fn account_balances(account: &serde_json::Value) -> Vec<(String, f64)> {
+    account
+        .get("balances")
+        .and_then(|b| b.as_array())
+        .map(|balances| {
+            balances
+                .iter()
+                .filter_map(|b| {
+                    let asset = b.get("asset")?.as_str()?.to_string();
+                    let num = |key: &str| {
+                        b.get(key)
+                            .and_then(|v| v.as_str())
+                            .and_then(|v| v.parse::<f64>().ok())
+                            .unwrap_or(0.0)
+                    };
+                    Some((asset, num("free") + num("locked")))
+                })
+                .filter(|(_, amount)| *amount > 0.0)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Realized and unrealized paper PnL combined into one figure.
+///
+/// Both legs use the configured cost basis method, as `get_asset_cost_basis` does.
+#[tauri::command]
+pub async fn get_total_pnl() -> Result<TotalPnl, String> {
+    let method = ledger::cost_basis_method()?;
+    let mut by_asset: HashMap<String, Vec<Trade>> = HashMap::new();
+    for trade in ledger::load_trades(None)? {
+        by_asset.entry(trade.asset.to_uppercase()).or_default().push(trade);
+    }
+    let replays: HashMap<String, ledger::Replay> = by_asset
+        .into_iter()
+        .map(|(asset, trades)| (asset, ledger::replay_trades(&trades, &method)))
+        .collect();
+    let realized: f64 = replays.values().map(|r| r.realized).sum();
+    let realized_cost: f64 = replays.values().map(|r| r.realized_cost).sum();
+    
+    let (wallet, prices, _) = current_wallet_value().await?;
+    let mut holdings_value = 0.0;
+    let mut cost_basis = 0.0;
+    let mut unpriced = Vec::new();
+    for h in wallet.holdings.values().filter(|h| !h.asset.eq_ignore_ascii_case("USDT") && h.amount > 0.0) {
+        let asset = h.asset.to_uppercase();
+        match prices.get(&asset) {
+            Some(price) => {
+                holdings_value += h.amount * price;
+                // Holdings with no ledger history keep the wallet's own basis
+                cost_basis += replays.get(&asset).map(|r| r.remaining_cost).unwrap_or(h.total_cost);
+            }
+            None => unpriced.push(asset),
+        }
+    }
+    unpriced.sort();
+    
+    let unrealized = holdings_value - cost_basis;
+    let total = realized + unrealized;
+    let total_invested = realized_cost + cost_basis;
+    
+    Ok(TotalPnl {
+        realized,
+        unrealized,
+        total,
+        total_invested,
+        total_percent: if total_invested > 0.0 { total / total_invested * 100.0 } else { 0.0 },
+        unpriced,
+    })
+}
+
 #[tauri::command]
-pub async fn get_live_portfolio(_exchange: String) -> Result<PortfolioData, String> {
-    // For now, return empty portfolio
-    // In a full implementation, this would use the exchange's REST API with stored credentials
+pub async fn get_live_portfolio(
+    state: State<'_, AppState>,
+    exchange: String,
+) -> Result<PortfolioData, CommandError> {
+    let account = exchanges::account_snapshot(&state, &exchange).await?;
+    let balances = account_balances(&account);
+    
+    let assets: Vec<String> = balances
+        .iter()
+        .filter(|(asset, _)| !STABLECOINS.contains(&asset.as_str()))
+        .map(|(asset, _)| asset.clone())
+        .collect();
+    let price_map = prices::fetch_price_map(&assets).await?;
+    
+    let mut total_value = 0.0;
+    let holdings = balances
+        .into_iter()
+        .map(|(asset, amount)| {
+            let price = if STABLECOINS.contains(&asset.as_str()) {
+                1.0
+            } else {
+                price_map.get(&asset).copied().unwrap_or(0.0)
+            };
+            total_value += amount * price;
+            // Exchange balances carry no cost basis
+            Holding {
+                asset,
+                amount,
+                avg_buy_price: 0.0,
+                total_cost: 0.0,
+            }
+        })
+        .collect();
+    
     Ok(PortfolioData {
-        total_value: 0.0,
-        holdings: vec![],
+        total_value,
+        holdings,
+    })
+}
+
+/// Combine the paper holding and every supported exchange's balance of `asset`.
+/// Testnet balances are listed but not counted in the totals.
+///
+/// Exchange balances carry no cost basis, so the average entry is weighted over
+/// the paper units only and the rest is reported as basis-unknown.
+#[tauri::command]
+pub async fn get_unified_position(state: State<'_, AppState>, asset: String) -> Result<UnifiedPosition, String> {
+    let asset = asset.trim().to_uppercase();
+    let price = if STABLECOINS.contains(&asset.as_str()) {
+        Some(1.0)
+    } else {
+        prices::fetch_price_map(std::slice::from_ref(&asset)).await?.get(&asset).copied()
+    };
+    let value_of = |units: f64| price.map(|p| units * p);
+    
+    let mut sources = Vec::new();
+    if let Some(h) = load_wallet()?.holdings.get(&asset).filter(|h| h.amount > 0.0) {
+        sources.push(PositionSource {
+            source: "paper".to_string(),
+            units: h.amount,
+            value: value_of(h.amount),
+            cost_basis: Some(h.total_cost),
+            average_entry: Some(h.avg_buy_price),
+            testnet: false,
+        });
+    }
+    
+    let mut errors = Vec::new();
+    let mut exchange_list: Vec<(String, bool)> = config::load_config()?
+        .exchanges
+        .into_iter()
+        // Only Binance has a live integration; other keys must not be sent to it
+        .filter(|(name, _)| exchanges::supports_live_portfolio(name))
+        .map(|(name, creds)| (name, creds.testnet))
+        .collect();
+    exchange_list.sort();
+    for (exchange, testnet) in exchange_list {
+        match exchanges::account_snapshot(&state, &exchange).await {
+            Ok(account) => {
+                let units: f64 = account_balances(&account)
+                    .into_iter()
+                    .filter(|(a, _)| a.eq_ignore_ascii_case(&asset))
+                    .map(|(_, amount)| amount)
+                    .sum();
+                if units > 0.0 {
+                    sources.push(PositionSource {
+                        source: exchange,
+                        units,
+                        value: value_of(units),
+                        cost_basis: None,
+                        average_entry: None,
+                        testnet,
+                    });
+                }
+            }
+            Err(e) => errors.push(format!("{}: {}", exchange, e)),
+        }
+    }
+    
+    let counted = || sources.iter().filter(|s| !s.testnet);
+    let total_units: f64 = counted().map(|s| s.units).sum();
+    let known_units: f64 = counted().filter(|s| s.cost_basis.is_some()).map(|s| s.units).sum();
+    let known_cost: f64 = counted().filter_map(|s| s.cost_basis).sum();
+    
+    Ok(UnifiedPosition {
+        asset,
+        price,
+        total_units,
+        total_value: value_of(total_units),
+        known_basis_units: known_units,
+        known_cost,
+        average_entry: (known_units > 0.0).then(|| known_cost / known_units),
+        unknown_basis_units: total_units - known_units,
+        sources,
+        errors,
+    })
+}
+
+#[tauri::command]
+pub async fn get_portfolio_change_24h() -> Result<PortfolioChange, String> {
+    let wallet = load_wallet()?;
+    let symbols: Vec<String> = wallet
+        .holdings
+        .values()
+        .map(|h| format!("{}USDT", h.asset.to_uppercase()))
+        .collect();
+    
+    let tickers = if symbols.is_empty() {
+        vec![]
+    } else {
+        prices::fetch_prices_from_binance(&symbols).await?
+    };
+    
+    // Idle USDT counts toward the value but contributes 0% change
+    let mut total_value = wallet.usdt;
+    let mut change_usdt = 0.0;
+    let mut excluded_assets = Vec::new();
+    
+    for holding in wallet.holdings.values() {
+        let asset = holding.asset.to_uppercase();
+        let ticker = tickers
+            .iter()
+            .find(|t| t.symbol.replace('/', "") == format!("{}USDT", asset));
+        
+        match ticker {
+            Some(t) if t.price > 0.0 => {
+                let value = holding.amount * t.price;
+                let value_24h_ago = value / (1.0 + t.change_24h / 100.0);
+                total_value += value;
+                change_usdt += value - value_24h_ago;
+            }
+            _ => excluded_assets.push(asset),
+        }
+    }
+    
+    let previous_value = total_value - change_usdt;
+    Ok(PortfolioChange {
+        total_value,
+        change_percent: if previous_value > 0.0 { change_usdt / previous_value * 100.0 } else { 0.0 },
+        change_usdt,
+        excluded_assets,
+    })
+}
+
+/// Realistic proceeds from market-selling the whole paper holding into the bid side.
+#[tauri::command]
+pub async fn estimate_exit_value(asset: String) -> Result<ExitEstimate, String> {
+    let asset = asset.to_uppercase();
+    let wallet = load_wallet()?;
+    let quantity = wallet
+        .holdings
+        .values()
+        .find(|h| h.asset.eq_ignore_ascii_case(&asset))
+        .map(|h| h.amount)
+        .filter(|amount| *amount > 0.0)
+        .ok_or_else(|| format!("No {} holding in the paper wallet", asset))?;
+    
+    let book = prices::fetch_order_book(&asset, 5000).await?;
+    let top_of_book = book.bids.first().map(|(p, _)| *p).ok_or("Order book has no bids")?;
+    let (filled_quantity, proceeds) = prices::walk_book(&book.bids, quantity);
+    let average_price = if filled_quantity > 0.0 { proceeds / filled_quantity } else { 0.0 };
+    
+    Ok(ExitEstimate {
+        asset,
+        quantity,
+        filled_quantity,
+        proceeds,
+        average_price,
+        top_of_book,
+        slippage_percent: (top_of_book - average_price) / top_of_book * 100.0,
+        partial: filled_quantity < quantity,
+    })
+}
+
+/// Rank the ways to buy `usd_amount` of `base` through the quote currencies the paper
+/// wallet holds, by units received after spread, book depth and taker fee.
+#[tauri::command]
+pub async fn best_acquisition_route(
+    state: State<'_, AppState>,
+    base: String,
+    usd_amount: f64,
+) -> Result<RoutePlan, String> {
+    if !usd_amount.is_finite() || usd_amount <= 0.0 {
+        return Err("Amount must be positive".to_string());
+    }
+    
+    let base = base.trim().to_uppercase();
+    let wallet = load_wallet()?;
+    let held = |asset: &str| -> f64 {
+        let holding = wallet
+            .holdings
+            .values()
+            .filter(|h| h.asset.eq_ignore_ascii_case(asset))
+            .map(|h| h.amount)
+            .sum::<f64>();
+        if asset == "USDT" { wallet.usdt + holding } else { holding }
+    };
+    
+    let info = prices::exchange_info(&state).await?;
+    let quotes: Vec<&str> = prices::QUOTE_ASSETS
+        .iter()
+        .copied()
+        .filter(|q| *q != base && held(q) > 0.0)
+        .filter(|q| info.get(&format!("{}{}", base, q)).is_some_and(|f| f.status == "TRADING"))
+        .collect();
+    if quotes.is_empty() {
+        return Err(format!("No {} pair is quoted in a currency the wallet holds", base));
+    }
+    
+    // Crypto quotes are converted through their USDT price
+    let crypto_quotes: Vec<String> = quotes
+        .iter()
+        .filter(|q| !prices::USD_STABLECOINS.contains(q))
+        .map(|q| q.to_string())
+        .collect();
+    let quote_prices = prices::fetch_price_map(&crypto_quotes).await?;
+    let fee_rate = config::load_config()?.fees.unwrap_or_default().taker();
+    
+    let mut routes = Vec::new();
+    for quote in quotes {
+        let quote_usd = if prices::USD_STABLECOINS.contains(&quote) {
+            1.0
+        } else {
+            match quote_prices.get(quote) {
+                Some(price) => *price,
+                None => continue,
+            }
+        };
+        
+        let symbol = format!("{}{}", base, quote);
+        let book = prices::fetch_order_book(&symbol, 5000).await?;
+        let (Some(&(best_bid, _)), Some(&(best_ask, _))) = (book.bids.first(), book.asks.first()) else {
+            continue;
+        };
+        let mid = (best_bid + best_ask) / 2.0;
+        
+        let quote_amount = usd_amount / quote_usd;
+        let (bought, spent) = prices::walk_book_quote(&book.asks, quote_amount);
+        if bought <= 0.0 {
+            continue;
+        }
+        // Binance takes the buy fee in the base asset
+        let fee = bought * fee_rate;
+        let quantity = bought - fee;
+        let held_balance = held(quote);
+        
+        routes.push(Route {
+            quote: quote.to_string(),
+            symbol,
+            quote_amount: spent,
+            quantity,
+            fee,
+            effective_price_usd: spent * quote_usd / quantity,
+            spread_percent: (best_ask - best_bid) / mid * 100.0,
+            cost_percent: (spent / quantity / mid - 1.0) * 100.0,
+            held_balance,
+            sufficient_balance: held_balance >= quote_amount,
+            partial: spent < quote_amount * (1.0 - 1e-9),
+        });
+    }
+    
+    // Compare whole fills first; a partial fill can't be the cheapest way to spend the amount
+    routes.sort_by(|a, b| a.partial.cmp(&b.partial).then(b.quantity.total_cmp(&a.quantity)));
+    let best = routes
+        .first()
+        .cloned()
+        .ok_or_else(|| format!("No order book liquidity to buy {}", base))?;
+    
+    Ok(RoutePlan {
+        base,
+        usd_amount,
+        best,
+        routes,
     })
 }
@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use chrono::Utc;
+
+use super::prices;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Prediction {
+    pub id: String,
+    pub symbol: String,
+    pub target: f64,
+    /// "up" (price reaches the target from below) or "down"
+    pub direction: String,
+    pub by_date: i64,
+    pub created_at: i64,
+    pub price_at_creation: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<i64>,
+    /// Whether the target was reached by `by_date`; None until resolved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hit: Option<bool>,
+    /// Highest (up) or lowest (down) price seen between creation and `by_date`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extreme_price: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PredictionAccuracy {
+    pub total: u32,
+    pub resolved: u32,
+    pub pending: u32,
+    pub hits: u32,
+    pub misses: u32,
+    /// Hits over resolved predictions; None until one has resolved
+    pub hit_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PredictionsFile {
+    predictions: Vec<Prediction>,
+}
+
+const CHECK_INTERVAL_SECS: u64 = 600;
+
+fn get_omnitrade_dir() -> PathBuf {
+    let home = dirs::home_dir().expect("Could not find home directory");
+    home.join(".omnitrade")
+}
+
+fn get_predictions_path() -> PathBuf {
+    get_omnitrade_dir().join("predictions.json")
+}
+
+fn load_predictions() -> Result<Vec<Prediction>, String> {
+    let path = get_predictions_path();
+    
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let file: PredictionsFile = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    
+    Ok(file.predictions)
+}
+
+fn save_predictions(predictions: &[Prediction]) -> Result<(), String> {
+    let omnitrade_dir = get_omnitrade_dir();
+    
+    // Ensure directory exists
+    if !omnitrade_dir.exists() {
+        fs::create_dir_all(&omnitrade_dir).map_err(|e| e.to_string())?;
+    }
+    
+    let file = PredictionsFile {
+        predictions: predictions.to_vec(),
+    };
+    
+    let content = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    fs::write(get_predictions_path(), content).map_err(|e| e.to_string())?;
+    
+    Ok(())
+}
+
+/// Score a prediction whose deadline has passed against hourly highs/lows up to `by_date`.
+/// Without any klines it stays pending, to be retried on a later check.
+async fn score(prediction: &mut Prediction) -> Result<(), String> {
+    let klines = prices::fetch_klines_between(&prediction.symbol, "1h", prediction.created_at, prediction.by_date).await?;
+    if klines.is_empty() {
+        return Err("No klines available yet; will retry".to_string());
+    }
+    
+    let (extreme, hit) = if prediction.direction == "up" {
+        let high = klines.iter().map(|k| k.high).fold(f64::NEG_INFINITY, f64::max);
+        (high, high >= prediction.target)
+    } else {
+        let low = klines.iter().map(|k| k.low).fold(f64::INFINITY, f64::min);
+        (low, low <= prediction.target)
+    };
+    
+    prediction.extreme_price = extreme.is_finite().then_some(extreme);
+    prediction.hit = Some(hit);
+    prediction.resolved_at = Some(Utc::now().timestamp_millis());
+    Ok(())
+}
+
+/// Resolve every prediction whose deadline has passed. Returns how many were resolved.
+async fn resolve_due_predictions() -> Result<u32, String> {
+    let now = Utc::now().timestamp_millis();
+    let due: Vec<Prediction> = load_predictions()?
+        .into_iter()
+        .filter(|p| p.hit.is_none() && p.by_date <= now)
+        .collect();
+    
+    let mut scored = Vec::new();
+    for mut prediction in due {
+        match score(&mut prediction).await {
+            Ok(()) => scored.push(prediction),
+            Err(e) => eprintln!("Failed to score prediction {}: {}", prediction.id, e),
+        }
+    }
+    if scored.is_empty() {
+        return Ok(0);
+    }
+    
+    // Scoring hits the network; reload so predictions added meanwhile are kept
+    let mut predictions = load_predictions()?;
+    let mut resolved = 0;
+    for prediction in scored {
+        if let Some(existing) = predictions.iter_mut().find(|p| p.id == prediction.id && p.hit.is_none()) {
+            *existing = prediction;
+            resolved += 1;
+        }
+    }
+    save_predictions(&predictions)?;
+    Ok(resolved)
+}
+
+/// Background task scoring predictions once their deadline passes.
+pub async fn prediction_loop() {
+    loop {
+        if let Err(e) = resolve_due_predictions().await {
+            eprintln!("Failed to resolve predictions: {}", e);
+        }
+        
+        tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+    }
+}
+
+/// Record a call that `symbol` will reach `target` moving `direction` ("up" or "down") by `by_date`.
+#[tauri::command]
+pub async fn add_prediction(symbol: String, target: f64, by_date: i64, direction: String) -> Result<Prediction, String> {
+    let direction = direction.trim().to_lowercase();
+    if direction != "up" && direction != "down" {
+        return Err(format!("Unknown direction: {} (expected up or down)", direction));
+    }
+    if !target.is_finite() || target <= 0.0 {
+        return Err("Target must be positive".to_string());
+    }
+    let now = Utc::now().timestamp_millis();
+    if by_date <= now {
+        return Err("The prediction date must be in the future".to_string());
+    }
+    
+    let binance_symbol = prices::to_binance_symbol(&symbol);
+    let current = prices::fetch_prices_from_binance(std::slice::from_ref(&binance_symbol))
+        .await?
+        .into_iter()
+        .find(|p| prices::to_binance_symbol(&p.symbol) == binance_symbol)
+        .map(|p| p.price)
+        .ok_or_else(|| format!("No price available for {}", symbol))?;
+    
+    // A target already on the far side of the price would score as an instant hit
+    if (direction == "up" && target <= current) || (direction == "down" && target >= current) {
+        return Err(format!(
+            "{} is already at {}; an \"{}\" target must be {} it",
+            symbol.to_uppercase(),
+            current,
+            direction,
+            if direction == "up" { "above" } else { "below" }
+        ));
+    }
+    
+    let prediction = Prediction {
+        id: format!("prediction_{}", now),
+        symbol: prices::normalize_symbol(&binance_symbol).unwrap_or(binance_symbol),
+        target,
+        direction,
+        by_date,
+        created_at: now,
+        price_at_creation: current,
+        resolved_at: None,
+        hit: None,
+        extreme_price: None,
+    };
+    
+    let mut predictions = load_predictions()?;
+    predictions.push(prediction.clone());
+    save_predictions(&predictions)?;
+    
+    Ok(prediction)
+}
+
+/// Predictions, newest first; `resolved` filters to scored (true) or pending (false) ones.
+#[tauri::command]
+pub async fn get_predictions(resolved: Option<bool>) -> Result<Vec<Prediction>, String> {
+    let mut predictions: Vec<Prediction> = load_predictions()?
+        .into_iter()
+        .filter(|p| resolved.map(|r| p.hit.is_some() == r).unwrap_or(true))
+        .collect();
+    predictions.sort_by_key(|p| std::cmp::Reverse(p.created_at));
+    Ok(predictions)
+}
+
+#[tauri::command]
+pub async fn get_prediction_accuracy() -> Result<PredictionAccuracy, String> {
+    let predictions = load_predictions()?;
+    let hits = predictions.iter().filter(|p| p.hit == Some(true)).count() as u32;
+    let misses = predictions.iter().filter(|p| p.hit == Some(false)).count() as u32;
+    let resolved = hits + misses;
+    
+    Ok(PredictionAccuracy {
+        total: predictions.len() as u32,
+        resolved,
+        pending: predictions.len() as u32 - resolved,
+        hits,
+        misses,
+        hit_rate: (resolved > 0).then(|| hits as f64 / resolved as f64),
+    })
+}
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use chrono::Utc;
+use tauri::State;
+
+use super::alerts;
+use super::dca::{self, DCAConfig};
+use super::prices;
+use super::watchlist;
+use crate::AppState;
+
+/// A shareable trading setup. Holds no credentials or run state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Preset {
+    pub version: u32,
+    pub exported_at: i64,
+    pub watchlist: Vec<String>,
+    pub alerts: Vec<PresetAlert>,
+    pub dca: Vec<PresetDca>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetAlert {
+    pub symbol: String,
+    pub condition: String,
+    pub target_price: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetDca {
+    pub asset: String,
+    pub amount: f64,
+    pub frequency: String,
+    #[serde(default)]
+    pub smart: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetImport {
+    pub watchlist: u32,
+    pub alerts: u32,
+    pub dca: u32,
+}
+
+fn validate_preset(preset: &Preset) -> Result<(), String> {
+    let invalid: Vec<&str> = preset
+        .watchlist
+        .iter()
+        .chain(preset.alerts.iter().map(|a| &a.symbol))
+        .filter(|s| prices::normalize_symbol(s).is_none())
+        .map(|s| s.as_str())
+        .collect();
+    if !invalid.is_empty() {
+        return Err(format!("Preset contains invalid symbols: {}", invalid.join(", ")));
+    }
+    
+    if let Some(a) = preset.alerts.iter().find(|a| a.condition != "above" && a.condition != "below") {
+        return Err(format!("Unknown alert condition: {}", a.condition));
+    }
+    if let Some(d) = preset.dca.iter().find(|d| !dca::FREQUENCIES.contains(&d.frequency.as_str())) {
+        return Err(format!("Unknown DCA frequency: {}", d.frequency));
+    }
+    if preset.alerts.iter().any(|a| a.target_price <= 0.0) || preset.dca.iter().any(|d| d.amount <= 0.0) {
+        return Err("Preset prices and amounts must be positive".to_string());
+    }
+    Ok(())
+}
+
+/// Write the watchlist, alerts and DCA plans to a preset file. Secrets are never included.
+#[tauri::command]
+pub async fn export_preset(path: String) -> Result<(), String> {
+    let preset = Preset {
+        version: 1,
+        exported_at: Utc::now().timestamp_millis(),
+        watchlist: watchlist::load_watchlist(),
+        alerts: alerts::load_alerts()?
+            .into_iter()
+            // Import only accepts canonical conditions
+            .filter(|a| a.condition == "above" || a.condition == "below")
+            .map(|a| PresetAlert {
+                symbol: a.symbol,
+                condition: a.condition,
+                target_price: a.target_price,
+            })
+            .collect(),
+        dca: dca::load_dca_configs()?
+            .into_iter()
+            .map(|c| PresetDca {
+                asset: c.asset,
+                amount: c.amount,
+                frequency: c.frequency,
+                smart: c.smart,
+            })
+            .collect(),
+    };
+    
+    let content = serde_json::to_string_pretty(&preset).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+    
+    Ok(())
+}
+
+/// Load a preset, replacing the current setup or merging into it.
+///
+/// Imported alerts start armed; imported DCA plans start disabled so nothing
+/// buys until the user reviews them.
+#[tauri::command]
+pub async fn import_preset(
+    state: State<'_, AppState>,
+    path: String,
+    merge: bool,
+) -> Result<PresetImport, String> {
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let preset: Preset = serde_json::from_str(&content).map_err(|e| format!("Invalid preset: {}", e))?;
+    validate_preset(&preset)?;
+    
+    // Watchlist
+    let mut symbols = if merge { watchlist::load_watchlist() } else { vec![] };
+    symbols.extend(preset.watchlist.iter().cloned());
+    let symbols = watchlist::normalize_watchlist(&symbols);
+    watchlist::save_watchlist(&symbols)?;
+    state.watchlist_reload.notify_one();
+    
+    // Alerts, skipping ones already present when merging
+    let mut current_alerts = if merge { alerts::load_alerts()? } else { vec![] };
+    let mut added_alerts = 0;
+    for a in preset.alerts {
+        let duplicate = current_alerts.iter().any(|c| {
+            c.symbol.eq_ignore_ascii_case(&a.symbol) && c.condition == a.condition && c.target_price == a.target_price
+        });
+        if !duplicate {
+            current_alerts.push(alerts::new_alert(a.symbol, a.condition, a.target_price));
+            added_alerts += 1;
+        }
+    }
+    alerts::save_alerts(&current_alerts)?;
+    
+    // DCA plans
+    let mut configs = if merge { dca::load_dca_configs()? } else { vec![] };
+    let now = Utc::now().timestamp_millis();
+    let added_dca = preset.dca.len() as u32;
+    for (i, d) in preset.dca.into_iter().enumerate() {
+        configs.push(DCAConfig {
+            id: format!("dca_{}_{}", now, i),
+            asset: d.asset.to_uppercase(),
+            amount: d.amount,
+            frequency: d.frequency,
+            enabled: false,
+            last_run: None,
+            next_run: None,
+            executions: 0,
+            smart: d.smart,
+        });
+    }
+    dca::save_dca_configs(&configs)?;
+    
+    Ok(PresetImport {
+        watchlist: symbols.len() as u32,
+        alerts: added_alerts,
+        dca: added_dca,
+    })
+}
@@ -1,4 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+use super::exchanges;
+use super::mock;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -9,6 +16,125 @@ pub struct PriceData {
     pub volume_24h: f64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedPrices {
+    pub prices: Vec<PriceData>,
+    pub last_updated: Option<i64>,
+    pub cache_age_secs: Option<u64>,
+    pub is_stale: bool,
+}
+
+/// How often the background loop polls Binance.
+pub const PRICE_POLL_INTERVAL_SECS: u64 = 5;
+/// Poll interval while the exchange reports maintenance.
+pub const MAINTENANCE_POLL_INTERVAL_SECS: u64 = 60;
+
+/// Price feed performance for diagnostics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedMetrics {
+    pub last_latency_ms: Option<u64>,
+    /// Mean latency over the recent window
+    pub average_latency_ms: Option<f64>,
+    /// Fraction of recent fetches that succeeded, 0.0–1.0
+    pub success_rate: Option<f64>,
+    /// Fetches in the recent window
+    pub sample_count: u32,
+    pub total_successes: u64,
+    pub total_failures: u64,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub last_success_at: Option<i64>,
+}
+
+// Fetches kept for the windowed averages (~5 minutes at the normal poll interval)
+const FEED_METRICS_WINDOW: usize = 60;
+
+/// One price fetch: latency and whether it succeeded.
+#[derive(Debug, Clone, Copy)]
+struct FeedSample {
+    latency_ms: u64,
+    ok: bool,
+}
+
+/// Records each price loop fetch so feed health can be reported numerically.
+#[derive(Debug, Default)]
+pub struct FeedTracker {
+    samples: VecDeque<FeedSample>,
+    metrics: FeedMetrics,
+}
+
+impl FeedTracker {
+    pub fn record(&mut self, now: i64, latency_ms: u64, error: Option<String>) {
+        let ok = error.is_none();
+        self.samples.push_back(FeedSample { latency_ms, ok });
+        while self.samples.len() > FEED_METRICS_WINDOW {
+            self.samples.pop_front();
+        }
+        
+        let metrics = &mut self.metrics;
+        metrics.last_latency_ms = Some(latency_ms);
+        if ok {
+            metrics.total_successes += 1;
+            metrics.consecutive_failures = 0;
+            metrics.last_success_at = Some(now);
+        } else {
+            metrics.total_failures += 1;
+            metrics.consecutive_failures += 1;
+            metrics.last_error = error;
+        }
+    }
+    
+    pub fn metrics(&self) -> FeedMetrics {
+        let n = self.samples.len();
+        let mut metrics = self.metrics.clone();
+        metrics.sample_count = n as u32;
+        if n > 0 {
+            metrics.average_latency_ms = Some(self.samples.iter().map(|s| s.latency_ms as f64).sum::<f64>() / n as f64);
+            metrics.success_rate = Some(self.samples.iter().filter(|s| s.ok).count() as f64 / n as f64);
+        }
+        metrics
+    }
+}
+
+/// Staleness threshold from config, defaulting to 3× the poll interval.
+pub(crate) fn stale_after_secs() -> u64 {
+    crate::commands::config::load_config()
+        .ok()
+        .and_then(|c| c.price_stale_after_secs)
+        .unwrap_or(PRICE_POLL_INTERVAL_SECS * 3)
+}
+
+/// Age of the cache in seconds, or None if it has never been filled.
+pub(crate) fn cache_age_secs(last_updated: Option<i64>) -> Option<u64> {
+    last_updated.map(|t| ((chrono::Utc::now().timestamp_millis() - t).max(0) / 1000) as u64)
+}
+
+pub(crate) fn is_stale(last_updated: Option<i64>) -> bool {
+    cache_age_secs(last_updated)
+        .map(|age| age > stale_after_secs())
+        .unwrap_or(true)
+}
+
+/// Prices that moved more than `epsilon_percent` since the previous tick.
+/// Symbols with no previous price always count as changed.
+pub(crate) fn changed_prices<'a>(
+    previous: &[PriceData],
+    current: &'a [PriceData],
+    epsilon_percent: f64,
+) -> Vec<&'a PriceData> {
+    current
+        .iter()
+        .filter(|p| match previous.iter().find(|old| old.symbol == p.symbol) {
+            Some(old) if old.price > 0.0 => {
+                ((p.price - old.price) / old.price * 100.0).abs() > epsilon_percent
+            }
+            _ => true,
+        })
+        .collect()
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct BinanceTicker {
@@ -19,9 +145,14 @@ struct BinanceTicker {
 }
 
 pub async fn fetch_prices_from_binance(symbols: &[String]) -> Result<Vec<PriceData>, String> {
+    if mock::is_active() {
+        return Ok(mock::current_prices(symbols));
+    }
+    
     let symbols_json = serde_json::to_string(symbols).map_err(|e| e.to_string())?;
     let url = format!(
-        "https://api.binance.com/api/v3/ticker/24hr?symbols={}",
+        "{}/api/v3/ticker/24hr?symbols={}",
+        exchanges::api_base(false),
         urlencoding::encode(&symbols_json)
     );
     
@@ -31,6 +162,7 @@ pub async fn fetch_prices_from_binance(symbols: &[String]) -> Result<Vec<PriceDa
         .send()
         .await
         .map_err(|e| e.to_string())?;
+    exchanges::record_used_weight(&response);
     
     let tickers: Vec<BinanceTicker> = response.json().await.map_err(|e| e.to_string())?;
     
@@ -50,13 +182,882 @@ pub async fn fetch_prices_from_binance(symbols: &[String]) -> Result<Vec<PriceDa
     Ok(prices)
 }
 
+/// Hash a price payload so unchanged ticks can be detected cheaply.
+pub fn hash_prices(prices: &[PriceData]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for p in prices {
+        p.symbol.hash(&mut hasher);
+        p.price.to_bits().hash(&mut hasher);
+        p.change_24h.to_bits().hash(&mut hasher);
+        p.volume_24h.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
 fn format_symbol(binance_symbol: &str) -> String {
     // Convert BTCUSDT to BTC/USDT
-    if binance_symbol.ends_with("USDT") {
-        let base = &binance_symbol[..binance_symbol.len() - 4];
-        return format!("{}/USDT", base);
+    normalize_symbol(binance_symbol).unwrap_or_else(|| binance_symbol.to_string())
+}
+
+/// Quote assets recognised when splitting symbols, longest match first.
+pub const QUOTE_ASSETS: &[&str] = &["FDUSD", "USDT", "USDC", "BUSD", "BTC", "ETH", "BNB"];
+
+/// Base and quote asset per Binance symbol, from the last exchangeInfo fetch. Kept
+/// outside `AppState` because symbols are split in places without access to state.
+static SYMBOL_ASSETS: Mutex<Option<HashMap<String, (String, String)>>> = Mutex::new(None);
+
+fn remember_symbol_assets(info: &ExchangeInfo) {
+    let assets = info
+        .values()
+        .map(|f| (f.symbol.clone(), (f.base_asset.clone(), f.quote_asset.clone())))
+        .collect();
+    if let Ok(mut known) = SYMBOL_ASSETS.lock() {
+        *known = Some(assets);
+    }
+}
+
+/// Split by suffix against `QUOTE_ASSETS`, for when exchangeInfo hasn't been fetched.
+fn split_by_suffix(upper: &str) -> Option<(String, String)> {
+    let (base, quote) = match upper.split_once('/') {
+        Some((base, quote)) => (base.to_string(), quote.to_string()),
+        None => {
+            let quote = QUOTE_ASSETS
+                .iter()
+                .find(|q| upper.len() > q.len() && upper.ends_with(*q))?;
+            (upper[..upper.len() - quote.len()].to_string(), quote.to_string())
+        }
+    };
+    
+    if base.is_empty() || !QUOTE_ASSETS.contains(&quote.as_str()) {
+        return None;
+    }
+    Some((base, quote))
+}
+
+/// (base, quote) for "BTCUSDT", "btc/usdt", etc.
+///
+/// Uses exchangeInfo's `baseAsset`/`quoteAsset` once it has been fetched, so any
+/// listed quote works; before that, falls back to matching `QUOTE_ASSETS` suffixes.
+pub(crate) fn split_assets(symbol: &str) -> Result<(String, String), String> {
+    let upper = symbol.trim().to_uppercase();
+    let known = SYMBOL_ASSETS.lock().map_err(|e| e.to_string())?;
+    match known.as_ref() {
+        Some(assets) => assets
+            .get(&upper.replace('/', ""))
+            // "A/B" must name the listed split, not just concatenate to a listed symbol
+            .filter(|(base, quote)| upper.split_once('/').is_none_or(|(b, q)| b == base && q == quote))
+            .cloned()
+            .ok_or_else(|| format!("Unknown symbol: {}", symbol)),
+        None => split_by_suffix(&upper).ok_or_else(|| format!("Unrecognised quote asset in {}", symbol)),
+    }
+}
+
+/// Split a symbol into its base and quote asset, using exchangeInfo when reachable.
+#[tauri::command]
+pub async fn split_symbol(state: tauri::State<'_, crate::AppState>, symbol: String) -> Result<(String, String), String> {
+    // Populates the split table; without it the suffix heuristic still answers
+    if let Err(e) = exchange_info(&state).await {
+        eprintln!("exchangeInfo unavailable, splitting {} by suffix: {}", symbol, e);
+    }
+    split_assets(&symbol)
+}
+
+/// Normalize user input to Binance format: BTC → BTCUSDT, BTC/USDT → BTCUSDT
+pub fn to_binance_symbol(input: &str) -> String {
+    match split_assets(input) {
+        Ok((base, quote)) => format!("{}{}", base, quote),
+        Err(_) => format!("{}USDT", input.trim().to_uppercase().replace('/', "")),
+    }
+}
+
+/// Canonical "BASE/QUOTE" form (btcusdt, BTC/USDT, btc/usdt → BTC/USDT).
+///
+/// Returns None if the symbol can't be split into a base and quote asset.
+pub fn normalize_symbol(input: &str) -> Option<String> {
+    split_assets(input).ok().map(|(base, quote)| format!("{}/{}", base, quote))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Kline {
+    pub open_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+pub async fn fetch_klines(
+    symbol: &str,
+    interval: &str,
+    start_time: Option<i64>,
+    limit: u32,
+) -> Result<Vec<Kline>, String> {
+    let mut url = format!(
+        "{}/api/v3/klines?symbol={}&interval={}&limit={}",
+        exchanges::api_base(false),
+        to_binance_symbol(symbol),
+        interval,
+        limit.min(1000)
+    );
+    if let Some(start) = start_time {
+        url.push_str(&format!("&startTime={}", start));
     }
-    binance_symbol.to_string()
+    
+    let response = reqwest::get(&url).await.map_err(|e| e.to_string())?;
+    exchanges::record_used_weight(&response);
+    if !response.status().is_success() {
+        return Err(format!("Klines fetch failed: {}", response.status()));
+    }
+    
+    // Binance returns each kline as a heterogeneous array
+    let raw: Vec<Vec<serde_json::Value>> = response.json().await.map_err(|e| e.to_string())?;
+    let field = |k: &[serde_json::Value], i: usize| -> f64 {
+        k.get(i)
+            .and_then(|v| v.as_str())
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0.0)
+    };
+    
+    Ok(raw
+        .iter()
+        .map(|k| Kline {
+            open_time: k.first().and_then(|v| v.as_i64()).unwrap_or(0),
+            open: field(k, 1),
+            high: field(k, 2),
+            low: field(k, 3),
+            close: field(k, 4),
+            volume: field(k, 5),
+        })
+        .collect())
+}
+
+/// Fetch every kline from `start_time` to now, paging through Binance's 1000-candle limit.
+pub async fn fetch_all_klines(symbol: &str, interval: &str, start_time: i64) -> Result<Vec<Kline>, String> {
+    fetch_klines_between(symbol, interval, start_time, i64::MAX).await
+}
+
+/// Fetch every kline opened from `start_time` through `end_time`, stopping once past it.
+pub async fn fetch_klines_between(symbol: &str, interval: &str, start_time: i64, end_time: i64) -> Result<Vec<Kline>, String> {
+    let mut all: Vec<Kline> = Vec::new();
+    let mut start = start_time;
+    
+    loop {
+        let page = fetch_klines(symbol, interval, Some(start), 1000).await?;
+        let done = page.len() < 1000;
+        let Some(last_open) = page.last().map(|k| k.open_time) else {
+            break;
+        };
+        start = last_open + 1;
+        all.extend(page.into_iter().filter(|k| k.open_time <= end_time));
+        if done || last_open >= end_time {
+            break;
+        }
+    }
+    
+    Ok(all)
+}
+
+/// The last kline opened at or before `time`, or the first one if `time` precedes them all.
+pub fn kline_at(klines: &[Kline], time: i64) -> Option<&Kline> {
+    klines
+        .iter()
+        .take_while(|k| k.open_time <= time)
+        .last()
+        .or_else(|| klines.first())
+}
+
+/// Close of the last kline opened at or before `time`.
+pub fn price_at(klines: &[Kline], time: i64) -> Option<f64> {
+    kline_at(klines, time).map(|k| k.close)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderBook {
+    /// (price, quantity), best first
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceDepth {
+    bids: Vec<(String, String)>,
+    asks: Vec<(String, String)>,
+}
+
+pub async fn fetch_order_book(symbol: &str, limit: u32) -> Result<OrderBook, String> {
+    let url = format!(
+        "{}/api/v3/depth?symbol={}&limit={}",
+        exchanges::api_base(false),
+        to_binance_symbol(symbol),
+        limit.min(5000)
+    );
+    
+    let response = reqwest::get(&url).await.map_err(|e| e.to_string())?;
+    exchanges::record_used_weight(&response);
+    if !response.status().is_success() {
+        return Err(format!("Order book fetch failed for {}: {}", symbol, response.status()));
+    }
+    let depth: BinanceDepth = response.json().await.map_err(|e| e.to_string())?;
+    
+    let parse = |levels: Vec<(String, String)>| -> Vec<(f64, f64)> {
+        levels
+            .into_iter()
+            .filter_map(|(p, q)| Some((p.parse().ok()?, q.parse().ok()?)))
+            .collect()
+    };
+    
+    Ok(OrderBook {
+        bids: parse(depth.bids),
+        asks: parse(depth.asks),
+    })
+}
+
+/// Walk ask levels spending up to `quote_amount`, returning (quantity bought, quote spent).
+pub fn walk_book_quote(levels: &[(f64, f64)], quote_amount: f64) -> (f64, f64) {
+    let mut remaining = quote_amount;
+    let mut quantity = 0.0;
+    for &(price, available) in levels {
+        if remaining <= 0.0 || price <= 0.0 {
+            break;
+        }
+        let take = (remaining / price).min(available);
+        quantity += take;
+        remaining -= take * price;
+    }
+    (quantity, quote_amount - remaining.max(0.0))
+}
+
+/// Walk book levels to fill `quantity`, returning (filled quantity, total quote value).
+pub fn walk_book(levels: &[(f64, f64)], quantity: f64) -> (f64, f64) {
+    let mut remaining = quantity;
+    let mut value = 0.0;
+    for &(price, available) in levels {
+        if remaining <= 0.0 {
+            break;
+        }
+        let take = remaining.min(available);
+        value += take * price;
+        remaining -= take;
+    }
+    (quantity - remaining.max(0.0), value)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolFilters {
+    pub symbol: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+    /// "TRADING", "BREAK", ...
+    pub status: String,
+    pub step_size: f64,
+    pub min_qty: f64,
+    pub min_notional: f64,
+}
+
+/// Parse LOT_SIZE and (MIN_)NOTIONAL filters from an exchangeInfo symbol entry.
+fn parse_symbol_filters(info: &serde_json::Value) -> Option<SymbolFilters> {
+    let symbol = info.get("symbol")?.as_str()?.to_string();
+    let num = |f: &serde_json::Value, key: &str| -> f64 {
+        f.get(key)
+            .and_then(|v| v.as_str())
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0.0)
+    };
+    
+    let text = |key: &str| info.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+    let mut filters = SymbolFilters {
+        symbol,
+        base_asset: text("baseAsset"),
+        quote_asset: text("quoteAsset"),
+        status: text("status"),
+        step_size: 0.0,
+        min_qty: 0.0,
+        min_notional: 0.0,
+    };
+    for f in info.get("filters")?.as_array()? {
+        match f.get("filterType").and_then(|t| t.as_str()) {
+            Some("LOT_SIZE") => {
+                filters.step_size = num(f, "stepSize");
+                filters.min_qty = num(f, "minQty");
+            }
+            Some("MIN_NOTIONAL") | Some("NOTIONAL") => {
+                filters.min_notional = num(f, "minNotional");
+            }
+            _ => {}
+        }
+    }
+    Some(filters)
+}
+
+/// Filters for every symbol, keyed by Binance symbol (e.g. "BTCUSDT").
+pub type ExchangeInfo = HashMap<String, SymbolFilters>;
+
+/// One entry of exchangeInfo's `rateLimits`, e.g. REQUEST_WEIGHT 6000 per 1 MINUTE.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimit {
+    /// "REQUEST_WEIGHT", "ORDERS" or "RAW_REQUESTS"
+    pub rate_limit_type: String,
+    /// "SECOND", "MINUTE" or "DAY"
+    pub interval: String,
+    pub interval_num: u32,
+    pub limit: u32,
+}
+
+/// Binance spot's published limits, used until exchangeInfo has been fetched.
+pub(crate) fn default_rate_limits() -> Vec<RateLimit> {
+    [
+        ("REQUEST_WEIGHT", "MINUTE", 1, 6000),
+        ("ORDERS", "SECOND", 10, 100),
+        ("ORDERS", "DAY", 1, 200_000),
+        ("RAW_REQUESTS", "MINUTE", 5, 61_000),
+    ]
+    .into_iter()
+    .map(|(rate_limit_type, interval, interval_num, limit)| RateLimit {
+        rate_limit_type: rate_limit_type.to_string(),
+        interval: interval.to_string(),
+        interval_num,
+        limit,
+    })
+    .collect()
+}
+
+fn parse_rate_limits(info: &serde_json::Value) -> Vec<RateLimit> {
+    info.get("rateLimits")
+        .and_then(|r| r.as_array())
+        .map(|limits| {
+            limits
+                .iter()
+                .filter_map(|l| {
+                    Some(RateLimit {
+                        rate_limit_type: l.get("rateLimitType")?.as_str()?.to_string(),
+                        interval: l.get("interval")?.as_str()?.to_string(),
+                        interval_num: l.get("intervalNum")?.as_u64()? as u32,
+                        limit: l.get("limit")?.as_u64()? as u32,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// exchangeInfo changes rarely (listings, filter tweaks)
+const EXCHANGE_INFO_TTL_MS: i64 = 86_400_000;
+
+async fn fetch_exchange_info() -> Result<(ExchangeInfo, Vec<RateLimit>), String> {
+    let response = reqwest::get(format!("{}/api/v3/exchangeInfo", exchanges::api_base(false)))
+        .await
+        .map_err(|e| e.to_string())?;
+    exchanges::record_used_weight(&response);
+    if !response.status().is_success() {
+        return Err(format!("exchangeInfo request failed: {}", response.status()));
+    }
+    let info: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let filters = info
+        .get("symbols")
+        .and_then(|s| s.as_array())
+        .map(|symbols| symbols.iter().filter_map(parse_symbol_filters))
+        .into_iter()
+        .flatten()
+        .map(|f| (f.symbol.clone(), f))
+        .collect();
+    Ok((filters, parse_rate_limits(&info)))
+}
+
+/// exchangeInfo for every symbol, cached for a day. Refreshing it also
+/// refreshes the rate limits kept in `AppState`.
+pub(crate) async fn exchange_info(state: &crate::AppState) -> Result<ExchangeInfo, String> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut cached = state.exchange_info.lock().await;
+    match cached.as_ref() {
+        Some((fetched_at, info)) if now - fetched_at < EXCHANGE_INFO_TTL_MS => Ok(info.clone()),
+        _ => {
+            let (info, rate_limits) = fetch_exchange_info().await?;
+            remember_symbol_assets(&info);
+            *cached = Some((now, info.clone()));
+            if !rate_limits.is_empty() {
+                *state.rate_limits.lock().await = rate_limits;
+            }
+            Ok(info)
+        }
+    }
+}
+
+/// Latency and success metrics for the background price feed.
+#[tauri::command]
+pub async fn get_feed_metrics(state: tauri::State<'_, crate::AppState>) -> Result<FeedMetrics, String> {
+    Ok(state.feed_metrics.lock().await.metrics())
+}
+
+/// The running background price loop and the signal that asks it to exit.
+pub struct PriceLoop {
+    pub shutdown: std::sync::Arc<tokio::sync::Notify>,
+    pub task: tauri::async_runtime::JoinHandle<()>,
+}
+
+/// Stop the background price loop, wait for it to exit, and start a fresh one on the
+/// current config. Holding the lock throughout keeps two loops from ever running at once.
+#[tauri::command]
+pub async fn restart_price_loop(app: tauri::AppHandle, state: tauri::State<'_, crate::AppState>) -> Result<(), String> {
+    let mut current = state.price_loop.lock().await;
+    if let Some(running) = current.take() {
+        running.shutdown.notify_one();
+        // The loop returns cleanly on shutdown, so an error here means it panicked
+        if let Err(e) = running.task.await {
+            eprintln!("Price loop ended abnormally: {}", e);
+        }
+    }
+    
+    *current = Some(crate::spawn_price_loop(app));
+    Ok(())
+}
+
+/// Binance's published rate limits, as parsed from exchangeInfo (the defaults if it lists none).
+#[tauri::command]
+pub async fn get_rate_limits(state: tauri::State<'_, crate::AppState>) -> Result<Vec<RateLimit>, String> {
+    exchange_info(&state).await?;
+    let limits = state.rate_limits.lock().await.clone();
+    Ok(if limits.is_empty() { default_rate_limits() } else { limits })
+}
+
+/// LOT_SIZE and notional filters for a symbol, from the cached exchangeInfo.
+pub(crate) async fn symbol_filters(state: &crate::AppState, symbol: &str) -> Result<SymbolFilters, String> {
+    exchange_info(state)
+        .await?
+        .remove(&to_binance_symbol(symbol))
+        .ok_or_else(|| format!("Unknown symbol: {}", symbol))
+}
+
+/// Round a quantity up to the exchange step size.
+pub fn round_up_to_step(quantity: f64, step_size: f64) -> f64 {
+    if step_size <= 0.0 {
+        return quantity;
+    }
+    let steps = (quantity / step_size - 1e-9).ceil();
+    steps * step_size
+}
+
+/// Round a quantity down to the exchange step size.
+pub fn round_to_step(quantity: f64, step_size: f64) -> f64 {
+    if step_size <= 0.0 {
+        return quantity;
+    }
+    // Small epsilon guards against 0.3 / 0.1 = 2.9999999
+    let steps = (quantity / step_size + 1e-9).floor();
+    steps * step_size
+}
+
+#[derive(Debug, Deserialize)]
+struct BinancePrice {
+    symbol: String,
+    price: String,
+}
+
+/// Fetch last prices for a set of assets against USDT, keyed by asset (e.g. "BTC").
+pub async fn fetch_price_map(assets: &[String]) -> Result<HashMap<String, f64>, String> {
+    if mock::is_active() {
+        return Ok(mock::current_price_map());
+    }
+    
+    let mut map = HashMap::new();
+    let symbols: Vec<String> = assets
+        .iter()
+        .filter(|a| a.to_uppercase() != "USDT")
+        .map(|a| format!("{}USDT", a.to_uppercase()))
+        .collect();
+    
+    if symbols.is_empty() {
+        return Ok(map);
+    }
+    
+    let symbols_json = serde_json::to_string(&symbols).map_err(|e| e.to_string())?;
+    let url = format!(
+        "{}/api/v3/ticker/price?symbols={}",
+        exchanges::api_base(false),
+        urlencoding::encode(&symbols_json)
+    );
+    
+    let mut response = reqwest::get(&url).await.map_err(|e| e.to_string())?;
+    exchanges::record_used_weight(&response);
+    if response.status() == reqwest::StatusCode::BAD_REQUEST {
+        // One unlisted asset fails the whole batch — fall back to every ticker
+        response = reqwest::get(format!("{}/api/v3/ticker/price", exchanges::api_base(false)))
+            .await
+            .map_err(|e| e.to_string())?;
+        exchanges::record_used_weight(&response);
+    }
+    if !response.status().is_success() {
+        return Err(format!("Price fetch failed: {}", response.status()));
+    }
+    let prices: Vec<BinancePrice> = response.json().await.map_err(|e| e.to_string())?;
+    
+    for p in prices.into_iter().filter(|p| symbols.contains(&p.symbol)) {
+        if let (Some(asset), Ok(price)) = (p.symbol.strip_suffix("USDT"), p.price.parse::<f64>()) {
+            map.insert(asset.to_string(), price);
+        }
+    }
+    
+    Ok(map)
+}
+
+/// Stablecoins valued at $1 when resolving USD prices.
+pub const USD_STABLECOINS: &[&str] = &["USDT", "USDC", "FDUSD", "BUSD", "DAI", "TUSD"];
+
+/// The pair an asset is priced through and its resulting USD price.
+#[derive(Debug, Clone)]
+pub struct ResolvedPrice {
+    pub quote: String,
+    pub usd_price: f64,
+}
+
+/// Price each asset in USD via the first listed, trading pair in `QUOTE_ASSETS`
+/// preference order (stable quotes before BTC/ETH/BNB), converting crypto quotes
+/// through their USDT pair. Assets with no usable pair are left out.
+pub(crate) async fn resolve_usd_prices(
+    state: &crate::AppState,
+    assets: &[String],
+) -> Result<HashMap<String, ResolvedPrice>, String> {
+    if mock::is_active() {
+        return Ok(mock::current_price_map()
+            .into_iter()
+            .map(|(asset, usd_price)| (asset, ResolvedPrice { quote: "USDT".to_string(), usd_price }))
+            .collect());
+    }
+    
+    let info = exchange_info(state).await?;
+    let response = reqwest::get(format!("{}/api/v3/ticker/price", exchanges::api_base(false)))
+        .await
+        .map_err(|e| e.to_string())?;
+    exchanges::record_used_weight(&response);
+    if !response.status().is_success() {
+        return Err(format!("Price fetch failed: {}", response.status()));
+    }
+    let tickers: HashMap<String, f64> = response
+        .json::<Vec<BinancePrice>>()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter_map(|p| Some((p.symbol, p.price.parse::<f64>().ok()?)))
+        .collect();
+    
+    let quote_usd = |quote: &str| -> Option<f64> {
+        if USD_STABLECOINS.contains(&quote) {
+            Some(1.0)
+        } else {
+            tickers.get(&format!("{}USDT", quote)).copied()
+        }
+    };
+    let mut quotes: Vec<&str> = QUOTE_ASSETS.to_vec();
+    quotes.sort_by_key(|q| !USD_STABLECOINS.contains(q));
+    
+    let mut resolved = HashMap::new();
+    for asset in assets.iter().map(|a| a.to_uppercase()) {
+        let found = quotes.iter().find_map(|quote| {
+            let symbol = format!("{}{}", asset, quote);
+            let filters = info.get(&symbol).filter(|f| f.status == "TRADING")?;
+            let price = tickers.get(&filters.symbol)?;
+            Some(ResolvedPrice {
+                quote: quote.to_string(),
+                usd_price: price * quote_usd(quote)?,
+            })
+        });
+        if let Some(found) = found {
+            resolved.insert(asset, found);
+        }
+    }
+    
+    Ok(resolved)
+}
+
+/// All-time high as seen on this exchange's daily candles — not a global ATH
+/// across venues, and only since the pair was listed (or `since`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AthInfo {
+    pub symbol: String,
+    pub exchange: String,
+    /// Start of the candle history the ATH was computed over
+    pub history_start: i64,
+    pub ath: f64,
+    pub ath_time: i64,
+    pub current: f64,
+    pub percent_below: f64,
+}
+
+const ATH_CACHE_TTL_MS: i64 = 86_400_000;
+
+#[tauri::command]
+pub async fn get_ath_distance(
+    state: tauri::State<'_, crate::AppState>,
+    symbol: String,
+    since: Option<i64>,
+) -> Result<AthInfo, String> {
+    let key = format!("{}:{}", to_binance_symbol(&symbol), since.unwrap_or(0));
+    let now = chrono::Utc::now().timestamp_millis();
+    
+    if let Some((fetched_at, info)) = state.ath_cache.lock().await.get(&key) {
+        if now - fetched_at < ATH_CACHE_TTL_MS {
+            return Ok(info.clone());
+        }
+    }
+    
+    let klines = fetch_all_klines(&symbol, "1d", since.unwrap_or(0)).await?;
+    let peak = klines
+        .iter()
+        .max_by(|a, b| a.high.total_cmp(&b.high))
+        .ok_or_else(|| format!("No price history for {}", symbol))?;
+    let current = klines.last().map(|k| k.close).unwrap_or(0.0);
+    
+    let info = AthInfo {
+        symbol: normalize_symbol(&symbol).unwrap_or(symbol),
+        exchange: "binance".to_string(),
+        history_start: klines[0].open_time,
+        ath: peak.high,
+        ath_time: peak.open_time,
+        current,
+        percent_below: if peak.high > 0.0 { (peak.high - current) / peak.high * 100.0 } else { 0.0 },
+    };
+    
+    state.ath_cache.lock().await.insert(key, (now, info.clone()));
+    Ok(info)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtendedChanges {
+    pub symbol: String,
+    pub price: f64,
+    pub change_24h: f64,
+    pub change_7d: Option<f64>,
+    pub change_30d: Option<f64>,
+    /// Since the first daily open of the current UTC year
+    pub change_ytd: Option<f64>,
+}
+
+const EXTENDED_CHANGES_TTL_MS: i64 = 3_600_000;
+
+fn percent_change(from: f64, to: f64) -> Option<f64> {
+    (from > 0.0).then(|| (to - from) / from * 100.0)
+}
+
+/// 24h, 7d, 30d and year-to-date percentage changes for a symbol, cached for an hour.
+#[tauri::command]
+pub async fn get_extended_changes(
+    state: tauri::State<'_, crate::AppState>,
+    symbol: String,
+) -> Result<ExtendedChanges, String> {
+    let key = to_binance_symbol(&symbol);
+    let now = chrono::Utc::now();
+    let now_ms = now.timestamp_millis();
+    
+    if let Some((fetched_at, changes)) = state.extended_changes.lock().await.get(&key) {
+        if now_ms - fetched_at < EXTENDED_CHANGES_TTL_MS {
+            return Ok(changes.clone());
+        }
+    }
+    
+    let ticker = fetch_prices_from_binance(std::slice::from_ref(&key))
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("No price for {}", symbol))?;
+    // A year of daily candles covers 7d, 30d and YTD
+    let klines = fetch_klines(&key, "1d", None, 366).await?;
+    
+    // The last candle is today's; count back whole days from it
+    let close_days_ago = |days: usize| klines.len().checked_sub(days + 1).map(|i| klines[i].close);
+    let year_start = chrono::NaiveDate::from_ymd_opt(chrono::Datelike::year(&now), 1, 1)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|d| d.and_utc().timestamp_millis())
+        .unwrap_or(0);
+    let ytd_open = klines.iter().find(|k| k.open_time >= year_start).map(|k| k.open);
+    
+    let changes = ExtendedChanges {
+        symbol: format_symbol(&key),
+        price: ticker.price,
+        change_24h: ticker.change_24h,
+        change_7d: close_days_ago(7).and_then(|c| percent_change(c, ticker.price)),
+        change_30d: close_days_ago(30).and_then(|c| percent_change(c, ticker.price)),
+        change_ytd: ytd_open.and_then(|o| percent_change(o, ticker.price)),
+    };
+    
+    state.extended_changes.lock().await.insert(key, (now_ms, changes.clone()));
+    Ok(changes)
+}
+
+/// Number of polled prices kept per symbol for dashboard sparklines
+pub const SPARKLINE_LEN: usize = 60;
+
+/// Append the latest tick to each symbol's ring buffer, dropping symbols no longer polled.
+pub fn record_sparklines(buffers: &mut HashMap<String, VecDeque<f64>>, prices: &[PriceData]) {
+    buffers.retain(|symbol, _| prices.iter().any(|p| &p.symbol == symbol));
+    
+    for p in prices {
+        let buffer = buffers.entry(p.symbol.clone()).or_default();
+        if buffer.len() == SPARKLINE_LEN {
+            buffer.pop_front();
+        }
+        buffer.push_back(p.price);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceAnomaly {
+    pub symbol: String,
+    pub previous: f64,
+    pub price: f64,
+    pub jump_percent: f64,
+}
+
+/// Symbols whose new price moved more than `max_jump_percent` since the last buffered tick.
+pub fn detect_anomalies(
+    buffers: &HashMap<String, VecDeque<f64>>,
+    prices: &[PriceData],
+    max_jump_percent: f64,
+) -> Vec<PriceAnomaly> {
+    prices
+        .iter()
+        .filter_map(|p| {
+            let previous = *buffers.get(&p.symbol)?.back()?;
+            if previous <= 0.0 {
+                return None;
+            }
+            let jump_percent = (p.price - previous) / previous * 100.0;
+            (jump_percent.abs() > max_jump_percent).then(|| PriceAnomaly {
+                symbol: p.symbol.clone(),
+                previous,
+                price: p.price,
+                jump_percent,
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_price_sparkline(
+    state: tauri::State<'_, crate::AppState>,
+    symbol: String,
+) -> Result<Vec<f64>, String> {
+    let key = format_symbol(&to_binance_symbol(&symbol));
+    
+    state
+        .sparklines
+        .lock()
+        .await
+        .get(&key)
+        .map(|buffer| buffer.iter().copied().collect())
+        .ok_or_else(|| format!("No price history for {} (not on the watchlist?)", key))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinBuy {
+    pub symbol: String,
+    pub price: f64,
+    pub min_notional: f64,
+    pub min_qty: f64,
+    pub step_size: f64,
+    /// Smallest quantity satisfying LOT_SIZE and MIN_NOTIONAL at the current price
+    pub quantity: f64,
+    pub quote_amount: f64,
+}
+
+/// The smallest order the exchange will accept for a symbol right now.
+#[tauri::command]
+pub async fn get_min_buy(state: tauri::State<'_, crate::AppState>, symbol: String) -> Result<MinBuy, String> {
+    let filters = symbol_filters(&state, &symbol).await?;
+    let price = fetch_prices_from_binance(std::slice::from_ref(&filters.symbol))
+        .await?
+        .first()
+        .map(|p| p.price)
+        .filter(|p| *p > 0.0)
+        .ok_or_else(|| format!("No price for {}", symbol))?;
+    
+    let quantity = round_up_to_step(filters.min_qty.max(filters.min_notional / price), filters.step_size);
+    
+    Ok(MinBuy {
+        symbol: format_symbol(&filters.symbol),
+        price,
+        min_notional: filters.min_notional,
+        min_qty: filters.min_qty,
+        step_size: filters.step_size,
+        quantity,
+        quote_amount: quantity * price,
+    })
+}
+
+// Binance leveraged tokens (e.g. BTCUP, ETHBEAR)
+const LEVERAGED_SUFFIXES: &[&str] = &["UP", "DOWN", "BULL", "BEAR"];
+
+/// The most traded pairs for a quote asset, as "BASE/QUOTE", for a starter watchlist.
+#[tauri::command]
+pub async fn suggest_watchlist(
+    state: tauri::State<'_, crate::AppState>,
+    quote: String,
+    count: u32,
+) -> Result<Vec<String>, String> {
+    let quote = quote.trim().to_uppercase();
+    let count = count.clamp(1, 50) as usize;
+    let info = exchange_info(&state).await?;
+    
+    let trading: HashMap<&str, &SymbolFilters> = info
+        .values()
+        .filter(|f| f.quote_asset == quote && f.status == "TRADING")
+        .map(|f| (f.symbol.as_str(), f))
+        .collect();
+    if trading.is_empty() {
+        return Err(format!("No trading pairs quoted in {}", quote));
+    }
+    let bases: HashSet<&str> = trading.values().map(|f| f.base_asset.as_str()).collect();
+    // Only a suffix on top of a real base counts, so JUP isn't mistaken for J + UP
+    let is_leveraged = |base: &str| {
+        LEVERAGED_SUFFIXES
+            .iter()
+            .any(|suffix| base.strip_suffix(suffix).is_some_and(|b| bases.contains(b)))
+    };
+    
+    let response = reqwest::get(format!("{}/api/v3/ticker/24hr", exchanges::api_base(false)))
+        .await
+        .map_err(|e| e.to_string())?;
+    exchanges::record_used_weight(&response);
+    if !response.status().is_success() {
+        return Err(format!("Ticker fetch failed: {}", response.status()));
+    }
+    let tickers: Vec<BinanceTicker> = response.json().await.map_err(|e| e.to_string())?;
+    
+    let mut ranked: Vec<(&SymbolFilters, f64)> = tickers
+        .iter()
+        .filter_map(|t| {
+            let filters = *trading.get(t.symbol.as_str())?;
+            (!is_leveraged(&filters.base_asset)).then(|| (filters, t.quote_volume.parse().unwrap_or(0.0)))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    
+    Ok(ranked
+        .into_iter()
+        .take(count)
+        .map(|(f, _)| format!("{}/{}", f.base_asset, f.quote_asset))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_cached_prices(state: tauri::State<'_, crate::AppState>) -> Result<CachedPrices, String> {
+    let prices = state.prices_cache.lock().await.clone();
+    let last_updated = *state.last_updated.lock().await;
+    
+    Ok(CachedPrices {
+        prices,
+        last_updated,
+        cache_age_secs: cache_age_secs(last_updated),
+        is_stale: is_stale(last_updated),
+    })
 }
 
 #[tauri::command]
@@ -64,7 +1065,7 @@ pub async fn get_prices(symbols: Vec<String>) -> Result<Vec<PriceData>, String>
     // Convert symbols like "BTC/USDT" to "BTCUSDT" for Binance API
     let binance_symbols: Vec<String> = symbols
         .iter()
-        .map(|s| s.replace("/", ""))
+        .map(|s| to_binance_symbol(s))
         .collect();
     
     fetch_prices_from_binance(&binance_symbols).await
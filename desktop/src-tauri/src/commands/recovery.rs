@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use chrono::Utc;
+use tauri::{AppHandle, Emitter};
+
+use super::portfolio;
+
+// Data files the user may restore from a `.bak` copy
+const RESTORABLE_FILES: &[&str] = &["config.json", "alerts.json", "dca.json", "paper-wallet.json"];
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+// How long to wait before re-reading a file that looked empty or cut off
+const REREAD_DELAY: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    /// Backup file name within `~/.omnitrade`
+    pub file: String,
+    /// Data file it is a copy of
+    pub original: String,
+    pub created_at: i64,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataRecovered {
+    pub file: String,
+    pub moved_to: String,
+    pub error: String,
+}
+
+fn get_omnitrade_dir() -> PathBuf {
+    let home = dirs::home_dir().expect("Could not find home directory");
+    home.join(".omnitrade")
+}
+
+/// Register the app handle so loaders without one can still emit recovery events.
+pub fn init(app: AppHandle) {
+    let _ = APP_HANDLE.set(app);
+}
+
+/// Create `~/.omnitrade` and write default versions of any missing data files,
+/// so every loader finds a parseable file from the first run on.
+///
+/// config.json is left alone: the CLI treats a missing one as "setup needed", and
+/// an empty default would shadow a project-local `omnitrade.config.json`.
+pub fn init_app_data() -> Result<(), String> {
+    let omnitrade_dir = get_omnitrade_dir();
+    if !omnitrade_dir.exists() {
+        fs::create_dir_all(&omnitrade_dir).map_err(|e| e.to_string())?;
+    }
+    
+    let defaults = [
+        ("alerts.json", serde_json::json!({ "alerts": [] })),
+        ("dca.json", serde_json::json!({ "configs": [] })),
+        ("paper-wallet.json", serde_json::to_value(portfolio::fresh_wallet()).map_err(|e| e.to_string())?),
+    ];
+    
+    for (file, value) in defaults {
+        let path = omnitrade_dir.join(file);
+        if path.exists() {
+            continue;
+        }
+        let content = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
+        fs::write(&path, content).map_err(|e| e.to_string())?;
+    }
+    
+    Ok(())
+}
+
+/// Move an unparseable data file aside as `<name>.corrupt.<timestamp>`.
+///
+/// Callers continue with defaults; the frontend is told via `data-recovered`.
+pub(crate) fn quarantine_corrupt(path: &Path, error: &str) -> Result<(), String> {
+    let mut target = path.as_os_str().to_owned();
+    target.push(format!(".corrupt.{}", Utc::now().timestamp_millis()));
+    let target = PathBuf::from(target);
+    
+    fs::rename(path, &target).map_err(|e| e.to_string())?;
+    eprintln!("Corrupt data file {} moved to {}: {}", path.display(), target.display(), error);
+    
+    if let Some(app) = APP_HANDLE.get() {
+        let _ = app.emit("data-recovered", DataRecovered {
+            file: path.file_name().map(|f| f.to_string_lossy().to_string()).unwrap_or_default(),
+            moved_to: target.display().to_string(),
+            error: error.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Parse JSON from a data file, quarantining it and returning None if it's corrupt.
+///
+/// Only malformed JSON counts as corrupt. A file that parses but doesn't match the
+/// Rust types is likely valid data from the CLI or MCP server, so it stays put and
+/// the error is returned instead.
+///
+/// The CLI and daemon truncate a file before writing it, so an empty or cut-off read
+/// may just have raced a write. Those are re-read after a short delay, and only a
+/// syntax error that persists in a non-empty file is quarantined. A file that stays
+/// empty loads as None without being moved.
+pub(crate) fn parse_or_quarantine<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    content: &str,
+) -> Result<Option<T>, String> {
+    let first = match serde_json::from_str(content) {
+        Ok(value) => return Ok(Some(value)),
+        Err(e) => e,
+    };
+    if !(first.is_syntax() || first.is_eof() || content.trim().is_empty()) {
+        return Err(format!("{}: {}", path.display(), first));
+    }
+    
+    std::thread::sleep(REREAD_DELAY);
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    match serde_json::from_str(&content) {
+        Ok(value) => Ok(Some(value)),
+        Err(_) if content.trim().is_empty() => Ok(None),
+        Err(e) if e.is_syntax() => {
+            quarantine_corrupt(path, &e.to_string())?;
+            Ok(None)
+        }
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+/// The data file a backup belongs to: `<file>.bak`, `<file>.bak.<ts>` or `<file>.corrupt.<ts>`.
+fn backup_original(name: &str) -> Option<&'static str> {
+    RESTORABLE_FILES.iter().copied().find(|original| {
+        name.strip_prefix(original).is_some_and(|suffix| {
+            suffix == ".bak"
+                || suffix
+                    .strip_prefix(".bak.")
+                    .or_else(|| suffix.strip_prefix(".corrupt."))
+                    .is_some_and(|ts| !ts.is_empty() && ts.chars().all(|c| c.is_ascii_digit()))
+        })
+    })
+}
+
+/// Check that backup content is JSON with the top-level shape of the data file it
+/// claims to be. Fields are left to the loaders, since the CLI and MCP server write
+/// these files too and may add or omit fields the desktop types don't know about.
+fn validate_backup(original: &str, content: &str) -> Result<(), String> {
+    let value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| format!("Backup is not valid JSON: {}", e))?;
+    
+    let (key, is_expected): (&str, fn(&serde_json::Value) -> bool) = match original {
+        "config.json" => ("exchanges", serde_json::Value::is_object),
+        "alerts.json" => ("alerts", serde_json::Value::is_array),
+        "dca.json" => ("configs", serde_json::Value::is_array),
+        "paper-wallet.json" => ("holdings", serde_json::Value::is_object),
+        _ => return Err(format!("Unknown data file: {}", original)),
+    };
+    if !value.is_object() || !value.get(key).is_some_and(is_expected) {
+        return Err(format!("Backup is not a valid {}: missing \"{}\"", original, key));
+    }
+    Ok(())
+}
+
+/// Backups of the restorable data files in `~/.omnitrade`, newest first.
+#[tauri::command]
+pub async fn list_backups() -> Result<Vec<BackupInfo>, String> {
+    let dir = get_omnitrade_dir();
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+    
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(&dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let file = entry.file_name().to_string_lossy().to_string();
+        let Some(original) = backup_original(&file) else {
+            continue;
+        };
+        let metadata = entry.metadata().map_err(|e| e.to_string())?;
+        let created_at = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        
+        backups.push(BackupInfo {
+            file,
+            original: original.to_string(),
+            created_at,
+            size: metadata.len(),
+        });
+    }
+    
+    backups.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+    Ok(backups)
+}
+
+/// Swap a backup listed by `list_backups` back in.
+///
+/// The current file is first copied to `<file>.bak.<timestamp>` so the restore can be undone.
+#[tauri::command]
+pub async fn restore_backup(file: String) -> Result<(), String> {
+    let original = backup_original(&file).ok_or_else(|| format!("Not a backup file: {}", file))?;
+    let backup = get_omnitrade_dir().join(&file);
+    if !backup.exists() {
+        return Err(format!("Backup not found: {}", file));
+    }
+    
+    let content = fs::read_to_string(&backup).map_err(|e| e.to_string())?;
+    validate_backup(original, &content)?;
+    
+    let path = get_omnitrade_dir().join(original);
+    if path.exists() {
+        let current = get_omnitrade_dir().join(format!("{}.bak.{}", original, Utc::now().timestamp_millis()));
+        fs::copy(&path, current).map_err(|e| e.to_string())?;
+    }
+    
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn restore_from_backup(file: String) -> Result<(), String> {
+    if !RESTORABLE_FILES.contains(&file.as_str()) {
+        return Err(format!("Unknown data file: {}", file));
+    }
+    
+    let backup = format!("{}.bak", file);
+    if !get_omnitrade_dir().join(&backup).exists() {
+        return Err(format!("No backup found for {}", file));
+    }
+    
+    restore_backup(backup).await
+}
@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use chrono::{Datelike, Local, NaiveTime, TimeZone, Utc};
+use tauri::AppHandle;
+
+use super::config;
+use super::ledger;
+use super::notifications::{self, NotificationResult};
+use super::portfolio;
+use super::prices;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportState {
+    last_sent_at: Option<i64>,
+}
+
+const CHECK_INTERVAL_SECS: u64 = 60;
+const DAY_MS: i64 = 86_400_000;
+
+fn get_omnitrade_dir() -> PathBuf {
+    let home = dirs::home_dir().expect("Could not find home directory");
+    home.join(".omnitrade")
+}
+
+fn get_state_path() -> PathBuf {
+    get_omnitrade_dir().join("report-state.json")
+}
+
+fn load_state() -> Result<ReportState, String> {
+    let path = get_state_path();
+    
+    if !path.exists() {
+        return Ok(ReportState::default());
+    }
+    
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+fn save_state(state: &ReportState) -> Result<(), String> {
+    let omnitrade_dir = get_omnitrade_dir();
+    
+    // Ensure directory exists
+    if !omnitrade_dir.exists() {
+        fs::create_dir_all(&omnitrade_dir).map_err(|e| e.to_string())?;
+    }
+    
+    let content = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    fs::write(get_state_path(), content).map_err(|e| e.to_string())?;
+    
+    Ok(())
+}
+
+/// Most recent scheduled send time at or before now, in ms.
+fn last_occurrence(frequency: &str, time: &str) -> Result<Option<i64>, String> {
+    let time = NaiveTime::parse_from_str(time, "%H:%M")
+        .map_err(|_| format!("Invalid report time: {} (expected HH:MM)", time))?;
+    let now = Local::now();
+    
+    let mut date = now.date_naive();
+    if now.time() < time {
+        date = date.pred_opt().ok_or("Date out of range")?;
+    }
+    match frequency {
+        "daily" => {}
+        "weekly" => {
+            date = date - chrono::Days::new(date.weekday().num_days_from_monday() as u64);
+        }
+        "off" => return Ok(None),
+        _ => return Err(format!("Unknown report frequency: {} (expected daily, weekly or off)", frequency)),
+    }
+    
+    Ok(Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|d| d.timestamp_millis()))
+}
+
+/// Compose the report covering the last `period_days`.
+///
+/// Value changes are for current holdings, priced now and at the start of the period.
+/// Holdings without a price at either end are listed instead of failing the report.
+async fn compose_report(period_days: i64) -> Result<(String, String), String> {
+    let wallet = portfolio::load_wallet()?;
+    let assets: Vec<String> = wallet.holdings.values().map(|h| h.asset.to_uppercase()).collect();
+    let prices_now = prices::fetch_price_map(&assets).await?;
+    let since = Utc::now().timestamp_millis() - period_days * DAY_MS;
+    
+    // Idle USDT counts toward the value but contributes 0% change
+    let mut total_value = wallet.usdt;
+    let mut previous_value = wallet.usdt;
+    let mut movers: Vec<(String, f64)> = Vec::new();
+    let mut unpriced = Vec::new();
+    for holding in wallet.holdings.values() {
+        let asset = holding.asset.to_uppercase();
+        let price_then = prices::fetch_klines(&format!("{}USDT", asset), "1h", Some(since), 1)
+            .await
+            .ok()
+            .and_then(|k| k.first().map(|k| k.open));
+        match (prices_now.get(&asset), price_then) {
+            (Some(&now), Some(then)) if then > 0.0 => {
+                total_value += holding.amount * now;
+                previous_value += holding.amount * then;
+                movers.push((asset, (now / then - 1.0) * 100.0));
+            }
+            _ => unpriced.push(asset),
+        }
+    }
+    unpriced.sort();
+    let change = total_value - previous_value;
+    let change_percent = if previous_value > 0.0 { change / previous_value * 100.0 } else { 0.0 };
+    let top_mover = movers.iter().max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()));
+    
+    let realized: f64 = ledger::fifo_realized_lots(&ledger::load_trades(None)?)
+        .iter()
+        .filter(|l| l.disposed >= since)
+        .map(|l| l.gain)
+        .sum();
+    
+    let period = if period_days == 1 { "24h".to_string() } else { format!("{}d", period_days) };
+    let mut lines = vec![
+        format!("Total value: ${:.2}", total_value),
+        format!("{} change: {:+.2} ({:+.2}%)", period, change, change_percent),
+    ];
+    if let Some((asset, percent)) = top_mover {
+        lines.push(format!("Top mover: {}/USDT {:+.2}%", asset, percent));
+    }
+    lines.push(format!("Realized PnL ({}): {:+.2}", period, realized));
+    if !unpriced.is_empty() {
+        lines.push(format!("Not priced: {}", unpriced.join(", ")));
+    }
+    
+    let title = if period_days == 1 { "Daily portfolio report" } else { "Weekly portfolio report" };
+    Ok((title.to_string(), lines.join("\n")))
+}
+
+/// Send the scheduled report if its time has passed since the last one was sent.
+async fn send_due_report(app: &AppHandle) -> Result<(), String> {
+    let reports = config::load_config()?.reports.unwrap_or_default();
+    let frequency = reports.report_frequency.unwrap_or_else(|| "off".to_string());
+    let time = reports.report_time.unwrap_or_else(|| "09:00".to_string());
+    let Some(due_at) = last_occurrence(&frequency, &time)? else {
+        return Ok(());
+    };
+    
+    let mut state = load_state()?;
+    if state.last_sent_at.is_some_and(|sent| sent >= due_at) {
+        return Ok(());
+    }
+    
+    let (title, message) = compose_report(if frequency == "weekly" { 7 } else { 1 }).await?;
+    notifications::dispatch(app, &title, &message, false).await;
+    
+    state.last_sent_at = Some(Utc::now().timestamp_millis());
+    save_state(&state)
+}
+
+/// Background task sending scheduled portfolio reports.
+pub async fn report_loop(app: AppHandle) {
+    loop {
+        if let Err(e) = send_due_report(&app).await {
+            eprintln!("Failed to send portfolio report: {}", e);
+        }
+        
+        tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+    }
+}
+
+/// Compose and send the portfolio report immediately, regardless of schedule.
+#[tauri::command]
+pub async fn send_report_now(app: AppHandle) -> Result<Vec<NotificationResult>, String> {
+    let frequency = config::load_config()?
+        .reports
+        .and_then(|r| r.report_frequency)
+        .unwrap_or_default();
+    let (title, message) = compose_report(if frequency == "weekly" { 7 } else { 1 }).await?;
+    Ok(notifications::dispatch(&app, &title, &message, false).await)
+}
@@ -0,0 +1,554 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+
+use super::config;
+use super::exchanges;
+use super::portfolio;
+use super::prices;
+use super::watchlist;
+use crate::AppState;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionSize {
+    pub portfolio_value: f64,
+    pub risk_amount: f64,
+    pub quantity: f64,
+    pub notional: f64,
+    pub step_size: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundTripCost {
+    pub symbol: String,
+    pub bid: f64,
+    pub ask: f64,
+    pub spread_percent: f64,
+    pub taker_fee_rate: f64,
+    pub quantity: f64,
+    pub proceeds: f64,
+    pub spread_cost: f64,
+    pub fee_cost: f64,
+    pub total_cost: f64,
+    /// Price move needed just to break even
+    pub cost_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetExposure {
+    pub asset: String,
+    /// "spot" today; derivatives positions will report their own market type
+    pub market: String,
+    /// Signed notional in USDT: positive long, negative short
+    pub notional: f64,
+    pub percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Exposure {
+    pub portfolio_value: f64,
+    pub long_percent: f64,
+    pub short_percent: f64,
+    /// Long minus short; above 100% means leveraged
+    pub net_percent: f64,
+    pub gross_percent: f64,
+    pub positions: Vec<AssetExposure>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolatilityInfo {
+    pub symbol: String,
+    pub interval: String,
+    /// Number of returns the figures are computed from
+    pub samples: u32,
+    /// Standard deviation of per-candle log returns
+    pub period_volatility: f64,
+    pub annualized_volatility: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConcentrationInfo {
+    /// Herfindahl-Hirschman Index: sum of squared allocation weights, from 1/n to 1
+    pub hhi: f64,
+    /// 1 / HHI: how many equally sized positions would give the same concentration
+    pub effective_holdings: f64,
+    /// "highly concentrated", "moderately concentrated" or "well diversified"
+    pub rating: String,
+    pub positions: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteExposure {
+    pub usd: f64,
+    pub percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioImpact {
+    pub asset: String,
+    pub amount: f64,
+    pub shock_percent: f64,
+    pub price: f64,
+    pub shocked_price: f64,
+    pub current_value: f64,
+    pub projected_value: f64,
+    /// Projected minus current value
+    pub impact: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioResult {
+    pub current_value: f64,
+    pub projected_value: f64,
+    pub change: f64,
+    pub change_percent: f64,
+    /// Shock applied to holdings not named in the scenario
+    pub default_shock_percent: f64,
+    /// Largest loss first
+    pub assets: Vec<ScenarioImpact>,
+    /// Shocked assets the wallet doesn't hold, usually a typo
+    pub unused_shocks: Vec<String>,
+}
+
+/// Key in `simulate_scenario` shocks for the move applied to every unnamed asset.
+const DEFAULT_SHOCK_KEY: &str = "*";
+
+/// Size a long position so that a stop-out loses exactly `risk_percent` of the portfolio.
+///
+/// When `symbol` is given, the quantity is rounded down to that symbol's LOT_SIZE step.
+#[tauri::command]
+pub async fn compute_position_size(
+    state: State<'_, AppState>,
+    entry: f64,
+    stop: f64,
+    risk_percent: f64,
+    symbol: Option<String>,
+) -> Result<PositionSize, String> {
+    if entry <= 0.0 || stop <= 0.0 {
+        return Err("Entry and stop prices must be positive".to_string());
+    }
+    if stop >= entry {
+        return Err(format!(
+            "Stop ({}) must be below entry ({}) for a long position",
+            stop, entry
+        ));
+    }
+    if risk_percent <= 0.0 || risk_percent > 100.0 {
+        return Err("Risk percent must be between 0 and 100".to_string());
+    }
+    
+    let (_, _, portfolio_value) = portfolio::current_wallet_value().await?;
+    let risk_amount = portfolio_value * risk_percent / 100.0;
+    let mut quantity = risk_amount / (entry - stop);
+    
+    let step_size = match symbol {
+        Some(symbol) => {
+            let filters = prices::symbol_filters(&state, &symbol).await?;
+            quantity = prices::round_to_step(quantity, filters.step_size);
+            Some(filters.step_size)
+        }
+        None => None,
+    };
+    
+    Ok(PositionSize {
+        portfolio_value,
+        risk_amount,
+        quantity,
+        notional: quantity * entry,
+        step_size,
+    })
+}
+
+/// Cost of market-buying `usdt_amount` at the ask and immediately selling at the bid.
+#[tauri::command]
+pub async fn round_trip_cost(
+    state: State<'_, AppState>,
+    symbol: String,
+    usdt_amount: f64,
+    exchange: Option<String>,
+) -> Result<RoundTripCost, String> {
+    if usdt_amount <= 0.0 {
+        return Err("Amount must be positive".to_string());
+    }
+    
+    let book = prices::fetch_order_book(&symbol, 5)
+        .await
+        .map_err(|e| format!("Order book unavailable for {}: {}", symbol, e))?;
+    let (bid, ask) = match (book.bids.first(), book.asks.first()) {
+        (Some((bid, _)), Some((ask, _))) if *bid > 0.0 && *ask > 0.0 => (*bid, *ask),
+        _ => return Err(format!("Order book for {} is empty", symbol)),
+    };
+    
+    // Both legs are market orders, so both pay the taker fee. With an exchange (given
+    // or the default one), use the account's real fee tier instead of the configured rate.
+    let cfg = config::load_config()?;
+    let taker = match exchange.or_else(|| config::default_exchange(&cfg)) {
+        Some(exchange) => exchanges::fee_tier(&state, &exchange).await.taker,
+        None => cfg.fees.unwrap_or_default().taker(),
+    };
+    let quantity = usdt_amount / (ask * (1.0 + taker));
+    let buy_fee = quantity * ask * taker;
+    let gross_proceeds = quantity * bid;
+    let sell_fee = gross_proceeds * taker;
+    let proceeds = gross_proceeds - sell_fee;
+    let total_cost = usdt_amount - proceeds;
+    
+    Ok(RoundTripCost {
+        symbol: prices::normalize_symbol(&symbol).unwrap_or(symbol),
+        bid,
+        ask,
+        spread_percent: (ask - bid) / ask * 100.0,
+        taker_fee_rate: taker,
+        quantity,
+        proceeds,
+        spread_cost: quantity * (ask - bid),
+        fee_cost: buy_fee + sell_fee,
+        total_cost,
+        cost_percent: total_cost / usdt_amount * 100.0,
+    })
+}
+
+/// Market exposure of the paper portfolio as a share of its value.
+///
+/// Spot holdings are always long, so this tops out at 100% until derivatives
+/// positions are tracked.
+#[tauri::command]
+pub async fn get_exposure() -> Result<Exposure, String> {
+    let (wallet, prices, portfolio_value) = portfolio::current_wallet_value().await?;
+    let percent_of = |notional: f64| {
+        if portfolio_value > 0.0 { notional / portfolio_value * 100.0 } else { 0.0 }
+    };
+    
+    let mut positions: Vec<AssetExposure> = wallet
+        .holdings
+        .values()
+        .map(|h| {
+            let notional = h.amount * prices.get(&h.asset.to_uppercase()).copied().unwrap_or(0.0);
+            AssetExposure {
+                asset: h.asset.to_uppercase(),
+                market: "spot".to_string(),
+                notional,
+                percent: percent_of(notional),
+            }
+        })
+        .collect();
+    positions.sort_by(|a, b| b.notional.abs().total_cmp(&a.notional.abs()));
+    
+    let long: f64 = positions.iter().filter(|p| p.notional > 0.0).map(|p| p.notional).sum();
+    let short: f64 = positions.iter().filter(|p| p.notional < 0.0).map(|p| -p.notional).sum();
+    
+    Ok(Exposure {
+        portfolio_value,
+        long_percent: percent_of(long),
+        short_percent: percent_of(short),
+        net_percent: percent_of(long - short),
+        gross_percent: percent_of(long + short),
+        positions,
+    })
+}
+
+/// Candles per year for a Binance kline interval; crypto trades around the clock.
+fn periods_per_year(interval: &str) -> Option<f64> {
+    const MINUTES_PER_YEAR: f64 = 365.0 * 24.0 * 60.0;
+    let minutes = match interval {
+        "1m" => 1.0,
+        "3m" => 3.0,
+        "5m" => 5.0,
+        "15m" => 15.0,
+        "30m" => 30.0,
+        "1h" => 60.0,
+        "2h" => 120.0,
+        "4h" => 240.0,
+        "6h" => 360.0,
+        "8h" => 480.0,
+        "12h" => 720.0,
+        "1d" => 1440.0,
+        "3d" => 3.0 * 1440.0,
+        "1w" => 7.0 * 1440.0,
+        "1M" => return Some(12.0),
+        _ => return None,
+    };
+    Some(MINUTES_PER_YEAR / minutes)
+}
+
+/// Sample standard deviation of log returns between consecutive closes.
+fn log_return_stddev(closes: &[f64]) -> Option<f64> {
+    let returns: Vec<f64> = closes
+        .windows(2)
+        .filter(|w| w[0] > 0.0 && w[1] > 0.0)
+        .map(|w| (w[1] / w[0]).ln())
+        .collect();
+    if returns.len() < 2 {
+        return None;
+    }
+    
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    Some(variance.sqrt())
+}
+
+/// Realized volatility of `symbol` over the last `lookback` candles, per period and annualized.
+#[tauri::command]
+pub async fn get_volatility(symbol: String, interval: String, lookback: u32) -> Result<VolatilityInfo, String> {
+    if lookback < 2 {
+        return Err("Lookback must be at least 2 periods".to_string());
+    }
+    let per_year = periods_per_year(&interval).ok_or_else(|| format!("Unsupported interval: {}", interval))?;
+    
+    // One extra candle so `lookback` returns can be computed
+    let klines = prices::fetch_klines(&symbol, &interval, None, lookback.saturating_add(1)).await?;
+    let closes: Vec<f64> = klines.iter().map(|k| k.close).collect();
+    let period_volatility = log_return_stddev(&closes)
+        .ok_or_else(|| format!("Not enough price history for {}", symbol))?;
+    
+    Ok(VolatilityInfo {
+        symbol: symbol.to_uppercase(),
+        interval,
+        samples: closes.len().saturating_sub(1) as u32,
+        period_volatility,
+        annualized_volatility: period_volatility * per_year.sqrt(),
+    })
+}
+
+/// Pearson correlation of log returns between every pair of watchlist symbols.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrelationMatrix {
+    pub interval: String,
+    pub lookback: u32,
+    /// Row and column labels, in watchlist order
+    pub symbols: Vec<String>,
+    /// `matrix[i][j]` correlates `symbols[i]` with `symbols[j]`; None without enough overlap
+    pub matrix: Vec<Vec<Option<f64>>>,
+    /// Returns shared by every symbol, after aligning candles by open time
+    pub samples: u32,
+    pub computed_at: i64,
+}
+
+fn pearson(a: &[f64], b: &[f64]) -> Option<f64> {
+    let n = a.len().min(b.len());
+    if n < 2 {
+        return None;
+    }
+    let mean_a = a[..n].iter().sum::<f64>() / n as f64;
+    let mean_b = b[..n].iter().sum::<f64>() / n as f64;
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for i in 0..n {
+        let (da, db) = (a[i] - mean_a, b[i] - mean_b);
+        cov += da * db;
+        var_a += da * da;
+        var_b += db * db;
+    }
+    if var_a == 0.0 || var_b == 0.0 {
+        return None;
+    }
+    Some(cov / (var_a * var_b).sqrt())
+}
+
+/// Full correlation matrix for the watchlist, cached for one candle of `interval`.
+///
+/// Each symbol's klines are fetched once and aligned by open time, so every pair is
+/// compared over the same periods.
+#[tauri::command]
+pub async fn get_correlation_matrix(
+    state: State<'_, AppState>,
+    interval: String,
+    lookback: u32,
+) -> Result<CorrelationMatrix, String> {
+    if lookback < 2 {
+        return Err("Lookback must be at least 2 periods".to_string());
+    }
+    let per_year = periods_per_year(&interval).ok_or_else(|| format!("Unsupported interval: {}", interval))?;
+    let ttl_ms = (365.0 * 86_400_000.0 / per_year) as i64;
+    
+    let symbols: Vec<String> = watchlist::load_watchlist()
+        .iter()
+        .map(|s| prices::normalize_symbol(s).unwrap_or_else(|| s.to_uppercase()))
+        .collect();
+    if symbols.len() < 2 {
+        return Err("Need at least two watchlist symbols to correlate".to_string());
+    }
+    
+    let key = format!("{}:{}:{}", interval, lookback, symbols.join(","));
+    let now = chrono::Utc::now().timestamp_millis();
+    if let Some((computed_at, matrix)) = state.correlation_cache.lock().await.get(&key) {
+        if now - computed_at < ttl_ms {
+            return Ok(matrix.clone());
+        }
+    }
+    
+    // One extra candle so `lookback` returns can be computed
+    let mut closes: Vec<HashMap<i64, f64>> = Vec::new();
+    for symbol in &symbols {
+        let klines = prices::fetch_klines(symbol, &interval, None, lookback.saturating_add(1)).await?;
+        closes.push(klines.iter().map(|k| (k.open_time, k.close)).collect());
+    }
+    
+    let mut times: Vec<i64> = closes[0]
+        .keys()
+        .copied()
+        .filter(|t| closes.iter().all(|c| c.get(t).is_some_and(|p| *p > 0.0)))
+        .collect();
+    times.sort();
+    let returns: Vec<Vec<f64>> = closes
+        .iter()
+        .map(|c| times.windows(2).map(|w| (c[&w[1]] / c[&w[0]]).ln()).collect())
+        .collect();
+    
+    let matrix = (0..symbols.len())
+        .map(|i| {
+            (0..symbols.len())
+                .map(|j| if i == j { Some(1.0) } else { pearson(&returns[i], &returns[j]) })
+                .collect()
+        })
+        .collect();
+    
+    let result = CorrelationMatrix {
+        interval,
+        lookback,
+        symbols,
+        matrix,
+        samples: times.len().saturating_sub(1) as u32,
+        computed_at: now,
+    };
+    state.correlation_cache.lock().await.insert(key, (now, result.clone()));
+    Ok(result)
+}
+
+// Bucket for USDT and other stablecoins held outright rather than in a position
+const IDLE_STABLECOIN_BUCKET: &str = "STABLECOINS";
+
+/// Paper portfolio value grouped by the quote currency of the pair each holding
+/// is priced through, with idle stablecoins in their own bucket.
+///
+/// Holdings with no listed pair are left out.
+#[tauri::command]
+pub async fn get_exposure_by_quote(state: State<'_, AppState>) -> Result<HashMap<String, QuoteExposure>, String> {
+    let wallet = portfolio::load_wallet()?;
+    let assets: Vec<String> = wallet.holdings.values().map(|h| h.asset.to_uppercase()).collect();
+    let resolved = prices::resolve_usd_prices(&state, &assets).await?;
+    
+    let mut buckets: HashMap<String, f64> = HashMap::new();
+    *buckets.entry(IDLE_STABLECOIN_BUCKET.to_string()).or_default() += wallet.usdt;
+    for h in wallet.holdings.values() {
+        let asset = h.asset.to_uppercase();
+        if prices::USD_STABLECOINS.contains(&asset.as_str()) {
+            *buckets.entry(IDLE_STABLECOIN_BUCKET.to_string()).or_default() += h.amount;
+        } else if let Some(price) = resolved.get(&asset) {
+            *buckets.entry(price.quote.clone()).or_default() += h.amount * price.usd_price;
+        }
+    }
+    
+    let total: f64 = buckets.values().sum();
+    Ok(buckets
+        .into_iter()
+        .map(|(quote, usd)| {
+            let percent = if total > 0.0 { usd / total * 100.0 } else { 0.0 };
+            (quote, QuoteExposure { usd, percent })
+        })
+        .collect())
+}
+
+/// Project the paper portfolio's value if prices moved by the given percentages
+/// (e.g. `{ "BTC": -20, "*": -30 }`); `"*"` sets the shock for every other asset.
+///
+/// Idle USDT is unaffected. Holdings without a current price are left out.
+#[tauri::command]
+pub async fn simulate_scenario(shocks: HashMap<String, f64>) -> Result<ScenarioResult, String> {
+    let shocks: HashMap<String, f64> = shocks.into_iter().map(|(asset, shock)| (asset.trim().to_uppercase(), shock)).collect();
+    if let Some((asset, shock)) = shocks.iter().find(|(_, shock)| !shock.is_finite() || **shock < -100.0) {
+        return Err(format!("Shock for {} must be at least -100%, got {}", asset, shock));
+    }
+    let default_shock_percent = shocks.get(DEFAULT_SHOCK_KEY).copied().unwrap_or(0.0);
+    
+    let (wallet, prices, current_value) = portfolio::current_wallet_value().await?;
+    let mut amounts: HashMap<String, f64> = HashMap::new();
+    for h in wallet.holdings.values().filter(|h| h.amount > 0.0) {
+        *amounts.entry(h.asset.to_uppercase()).or_default() += h.amount;
+    }
+    
+    let mut assets: Vec<ScenarioImpact> = amounts
+        .iter()
+        .filter_map(|(asset, amount)| {
+            let price = *prices.get(asset)?;
+            let shock_percent = shocks.get(asset).copied().unwrap_or(default_shock_percent);
+            let shocked_price = price * (1.0 + shock_percent / 100.0);
+            Some(ScenarioImpact {
+                asset: asset.clone(),
+                amount: *amount,
+                shock_percent,
+                price,
+                shocked_price,
+                current_value: amount * price,
+                projected_value: amount * shocked_price,
+                impact: amount * (shocked_price - price),
+            })
+        })
+        .collect();
+    assets.sort_by(|a, b| a.impact.total_cmp(&b.impact));
+    
+    let mut unused_shocks: Vec<String> = shocks
+        .keys()
+        .filter(|asset| asset.as_str() != DEFAULT_SHOCK_KEY && !amounts.contains_key(*asset))
+        .cloned()
+        .collect();
+    unused_shocks.sort();
+    
+    let change: f64 = assets.iter().map(|a| a.impact).sum();
+    Ok(ScenarioResult {
+        current_value,
+        projected_value: current_value + change,
+        change,
+        change_percent: if current_value > 0.0 { change / current_value * 100.0 } else { 0.0 },
+        default_shock_percent,
+        assets,
+        unused_shocks,
+    })
+}
+
+/// Herfindahl-Hirschman Index over allocation weights (normalized to sum to 1).
+fn herfindahl(values: &[f64]) -> f64 {
+    let total: f64 = values.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    values.iter().map(|v| (v / total).powi(2)).sum()
+}
+
+/// Concentration of the paper portfolio, with idle USDT counted as its own position.
+#[tauri::command]
+pub async fn get_concentration() -> Result<ConcentrationInfo, String> {
+    let (wallet, prices, _) = portfolio::current_wallet_value().await?;
+    let mut values: Vec<f64> = wallet
+        .holdings
+        .values()
+        .map(|h| h.amount * prices.get(&h.asset.to_uppercase()).copied().unwrap_or(0.0))
+        .collect();
+    values.push(wallet.usdt);
+    values.retain(|v| *v > 0.0);
+    
+    let hhi = herfindahl(&values);
+    let rating = if hhi > 0.25 {
+        "highly concentrated"
+    } else if hhi > 0.15 {
+        "moderately concentrated"
+    } else {
+        "well diversified"
+    };
+    
+    Ok(ConcentrationInfo {
+        hhi,
+        effective_holdings: if hhi > 0.0 { 1.0 / hhi } else { 0.0 },
+        rating: rating.to_string(),
+        positions: values.len() as u32,
+    })
+}
@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use chrono::Utc;
+use tauri::{AppHandle, Emitter};
+
+use super::mock;
+use super::paper;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledOrder {
+    pub id: String,
+    pub asset: String,
+    pub usdt_amount: f64,
+    pub at_ms: i64,
+    pub created_at: i64,
+    pub status: String, // "pending", "executing", "executed", "failed", "cancelled"
+    pub executed_at: Option<i64>,
+    pub trade_id: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScheduledOrdersFile {
+    orders: Vec<ScheduledOrder>,
+}
+
+const CHECK_INTERVAL_SECS: u64 = 60;
+// Executions later than this after the scheduled time are flagged as late
+const LATE_GRACE_MS: i64 = 2 * 60_000;
+
+fn get_omnitrade_dir() -> PathBuf {
+    let home = dirs::home_dir().expect("Could not find home directory");
+    home.join(".omnitrade")
+}
+
+fn get_scheduled_path() -> PathBuf {
+    get_omnitrade_dir().join("scheduled-orders.json")
+}
+
+fn load_orders() -> Result<Vec<ScheduledOrder>, String> {
+    let path = get_scheduled_path();
+    
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let file: ScheduledOrdersFile = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    
+    Ok(file.orders)
+}
+
+fn save_orders(orders: &[ScheduledOrder]) -> Result<(), String> {
+    let omnitrade_dir = get_omnitrade_dir();
+    
+    // Ensure directory exists
+    if !omnitrade_dir.exists() {
+        fs::create_dir_all(&omnitrade_dir).map_err(|e| e.to_string())?;
+    }
+    
+    let file = ScheduledOrdersFile {
+        orders: orders.to_vec(),
+    };
+    
+    let content = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    fs::write(get_scheduled_path(), content).map_err(|e| e.to_string())?;
+    
+    Ok(())
+}
+
+/// Apply `change` to one order against a fresh read of the file, saving only if it
+/// returns Some. Re-reading keeps edits made meanwhile (e.g. a cancel) from being lost.
+fn update_order<T>(id: &str, change: impl FnOnce(&mut ScheduledOrder) -> Option<T>) -> Result<Option<T>, String> {
+    let mut orders = load_orders()?;
+    let Some(order) = orders.iter_mut().find(|o| o.id == id) else {
+        return Ok(None);
+    };
+    let result = change(order);
+    if result.is_some() {
+        save_orders(&orders)?;
+    }
+    Ok(result)
+}
+
+/// Execute every pending order whose time has come, including ones missed while the app was closed.
+///
+/// Each order is marked "executing" and saved before its buy, so a crash mid-run can't
+/// execute it twice; an order still "executing" on the next run was interrupted.
+async fn run_due_orders(app: &AppHandle) -> Result<(), String> {
+    // Leave due orders pending rather than filling them at fixture prices
+    if mock::is_active() {
+        return Ok(());
+    }
+    
+    let now = Utc::now().timestamp_millis();
+    let orders = load_orders()?;
+    
+    // Runs are sequential, so anything still executing was cut off by a crash or quit
+    for order in orders.iter().filter(|o| o.status == "executing") {
+        update_order(&order.id, |o| {
+            o.status = "failed".to_string();
+            o.note = Some("Interrupted while executing; check the trade ledger before rescheduling".to_string());
+            Some(())
+        })?;
+    }
+    
+    let due: Vec<String> = orders
+        .iter()
+        .filter(|o| o.status == "pending" && o.at_ms <= now)
+        .map(|o| o.id.clone())
+        .collect();
+    
+    for id in due {
+        // Claim the order; skip it if it was cancelled since the list was read
+        let claimed = update_order(&id, |o| {
+            (o.status == "pending").then(|| {
+                o.status = "executing".to_string();
+                o.clone()
+            })
+        })?;
+        let Some(order) = claimed else {
+            continue;
+        };
+        let late_by = now - order.at_ms;
+        
+        let result = paper::buy_with_usdt(&order.asset, order.usdt_amount).await;
+        let finished = update_order(&id, |o| {
+            match result {
+                Ok(trade) => {
+                    o.status = "executed".to_string();
+                    o.trade_id = Some(trade.id);
+                    if late_by > LATE_GRACE_MS {
+                        o.note = Some(format!(
+                            "Ran {} minutes late (app was not running at the scheduled time)",
+                            late_by / 60_000
+                        ));
+                    }
+                }
+                Err(e) => {
+                    o.status = "failed".to_string();
+                    o.note = Some(e.to_string());
+                }
+            }
+            o.executed_at = Some(now);
+            Some(o.clone())
+        })?;
+        if let Some(order) = finished {
+            let _ = app.emit("scheduled-order-executed", order);
+        }
+    }
+    
+    Ok(())
+}
+
+/// Background task checking for due scheduled orders every minute.
+pub async fn scheduled_orders_loop(app: AppHandle) {
+    loop {
+        if let Err(e) = run_due_orders(&app).await {
+            eprintln!("Failed to run scheduled orders: {}", e);
+        }
+        
+        tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+    }
+}
+
+#[tauri::command]
+pub async fn schedule_order(asset: String, usdt_amount: f64, at_ms: i64) -> Result<ScheduledOrder, String> {
+    if usdt_amount <= 0.0 {
+        return Err("Amount must be positive".to_string());
+    }
+    let now = Utc::now().timestamp_millis();
+    if at_ms <= now {
+        return Err("Scheduled time must be in the future".to_string());
+    }
+    
+    let mut orders = load_orders()?;
+    let order = ScheduledOrder {
+        id: format!("sched_{}", paper::trade_id()),
+        asset: asset.trim().to_uppercase(),
+        usdt_amount,
+        at_ms,
+        created_at: now,
+        status: "pending".to_string(),
+        executed_at: None,
+        trade_id: None,
+        note: None,
+    };
+    
+    orders.push(order.clone());
+    save_orders(&orders)?;
+    
+    Ok(order)
+}
+
+#[tauri::command]
+pub async fn cancel_scheduled_order(id: String) -> Result<(), String> {
+    let mut orders = load_orders()?;
+    let order = orders
+        .iter_mut()
+        .find(|o| o.id == id)
+        .ok_or_else(|| format!("Scheduled order not found: {}", id))?;
+    
+    if order.status != "pending" {
+        return Err(format!("Order already {}", order.status));
+    }
+    order.status = "cancelled".to_string();
+    
+    save_orders(&orders)
+}
+
+#[tauri::command]
+pub async fn list_scheduled_orders() -> Result<Vec<ScheduledOrder>, String> {
+    let mut orders = load_orders()?;
+    orders.sort_by_key(|o| o.at_ms);
+    Ok(orders)
+}
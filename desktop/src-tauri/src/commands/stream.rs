@@ -0,0 +1,260 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use futures_util::StreamExt;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::Mutex;
+use tokio_tungstenite::tungstenite::Message;
+
+use super::config;
+use super::exchanges;
+use crate::AppState;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamBalance {
+    pub asset: String,
+    pub free: f64,
+    pub locked: f64,
+}
+
+/// Parsed `outboundAccountPosition`: balances that changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountUpdate {
+    pub exchange: String,
+    pub event_time: i64,
+    pub balances: Vec<StreamBalance>,
+}
+
+/// Parsed `executionReport`: an order was placed, filled, cancelled, etc.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderUpdate {
+    pub exchange: String,
+    pub event_time: i64,
+    pub symbol: String,
+    pub order_id: i64,
+    pub client_order_id: String,
+    pub side: String,
+    pub order_type: String,
+    /// "NEW", "TRADE", "CANCELED", "EXPIRED", ...
+    pub execution_type: String,
+    /// "NEW", "PARTIALLY_FILLED", "FILLED", "CANCELED", ...
+    pub status: String,
+    pub price: f64,
+    pub quantity: f64,
+    pub last_filled_quantity: f64,
+    pub last_filled_price: f64,
+    pub cumulative_quantity: f64,
+    pub commission: f64,
+    pub commission_asset: Option<String>,
+}
+
+/// The running user data stream task and the listenKey it currently holds.
+pub struct UserStream {
+    exchange: String,
+    listen_key: Arc<Mutex<Option<String>>>,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+// Binance expires listenKeys after 60 minutes without a keepalive
+const KEEPALIVE_INTERVAL_SECS: u64 = 30 * 60;
+const RECONNECT_DELAY_SECS: u64 = 5;
+
+fn stream_base(testnet: bool) -> &'static str {
+    if testnet {
+        "wss://stream.testnet.binance.vision/ws"
+    } else {
+        "wss://stream.binance.com:9443/ws"
+    }
+}
+
+async fn create_listen_key(exchange: &str) -> Result<String, String> {
+    let body = exchanges::api_key_request(exchange, reqwest::Method::POST, "/api/v3/userDataStream", &[]).await?;
+    body.get("listenKey")
+        .and_then(|k| k.as_str())
+        .map(|k| k.to_string())
+        .ok_or_else(|| "No listenKey in response".to_string())
+}
+
+async fn keepalive_listen_key(exchange: &str, listen_key: &str) -> Result<(), String> {
+    let params = [("listenKey", listen_key.to_string())];
+    exchanges::api_key_request(exchange, reqwest::Method::PUT, "/api/v3/userDataStream", &params).await?;
+    Ok(())
+}
+
+async fn close_listen_key(exchange: &str, listen_key: &str) -> Result<(), String> {
+    let params = [("listenKey", listen_key.to_string())];
+    exchanges::api_key_request(exchange, reqwest::Method::DELETE, "/api/v3/userDataStream", &params).await?;
+    Ok(())
+}
+
+fn num(value: &serde_json::Value, key: &str) -> f64 {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0.0)
+}
+
+fn text(value: &serde_json::Value, key: &str) -> String {
+    value.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string()
+}
+
+fn parse_account_update(exchange: &str, event: &serde_json::Value) -> AccountUpdate {
+    AccountUpdate {
+        exchange: exchange.to_lowercase(),
+        event_time: event.get("E").and_then(|v| v.as_i64()).unwrap_or(0),
+        balances: event
+            .get("B")
+            .and_then(|b| b.as_array())
+            .map(|balances| {
+                balances
+                    .iter()
+                    .map(|b| StreamBalance {
+                        asset: text(b, "a"),
+                        free: num(b, "f"),
+                        locked: num(b, "l"),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+fn parse_order_update(exchange: &str, event: &serde_json::Value) -> OrderUpdate {
+    OrderUpdate {
+        exchange: exchange.to_lowercase(),
+        event_time: event.get("E").and_then(|v| v.as_i64()).unwrap_or(0),
+        symbol: text(event, "s"),
+        order_id: event.get("i").and_then(|v| v.as_i64()).unwrap_or(0),
+        client_order_id: text(event, "c"),
+        side: text(event, "S"),
+        order_type: text(event, "o"),
+        execution_type: text(event, "x"),
+        status: text(event, "X"),
+        price: num(event, "p"),
+        quantity: num(event, "q"),
+        last_filled_quantity: num(event, "l"),
+        last_filled_price: num(event, "L"),
+        cumulative_quantity: num(event, "z"),
+        commission: num(event, "n"),
+        commission_asset: event.get("N").and_then(|v| v.as_str()).map(|v| v.to_string()),
+    }
+}
+
+/// Forward one stream message to the frontend. Returns false once the listenKey has expired.
+async fn handle_message(app: &AppHandle, exchange: &str, raw: &str) -> bool {
+    let Ok(event) = serde_json::from_str::<serde_json::Value>(raw) else {
+        return true;
+    };
+    
+    match event.get("e").and_then(|e| e.as_str()) {
+        Some("outboundAccountPosition") => {
+            // Balances just changed, so a cached /account response is stale
+            if let Some(state) = app.try_state::<AppState>() {
+                state.account_cache.lock().await.remove(&exchange.to_lowercase());
+            }
+            let _ = app.emit("account-update", parse_account_update(exchange, &event));
+        }
+        Some("executionReport") => {
+            let _ = app.emit("order-update", parse_order_update(exchange, &event));
+        }
+        Some("listenKeyExpired") => return false,
+        _ => {}
+    }
+    true
+}
+
+/// One connection's lifetime: new listenKey, connect, relay events with keepalives.
+///
+/// Returns Ok when the stream ends normally (server close, key expiry) so the
+/// caller reconnects with a fresh key.
+async fn stream_session(app: &AppHandle, exchange: &str, current_key: &Mutex<Option<String>>) -> Result<(), String> {
+    let cfg = config::load_config()?;
+    let testnet = config::find_exchange(&cfg, exchange)
+        .ok_or_else(|| format!("Exchange not configured: {}", exchange))?
+        .testnet;
+    
+    let listen_key = create_listen_key(exchange).await?;
+    *current_key.lock().await = Some(listen_key.clone());
+    
+    let url = format!("{}/{}", stream_base(testnet), listen_key);
+    let (mut ws, _) = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    let mut keepalive = tokio::time::interval(Duration::from_secs(KEEPALIVE_INTERVAL_SECS));
+    // The first tick fires immediately; the key is brand new
+    keepalive.tick().await;
+    
+    loop {
+        tokio::select! {
+            _ = keepalive.tick() => keepalive_listen_key(exchange, &listen_key).await?,
+            message = ws.next() => match message {
+                Some(Ok(Message::Text(raw))) => {
+                    if !handle_message(app, exchange, &raw).await {
+                        return Ok(());
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => return Ok(()),
+                Some(Err(e)) => return Err(e.to_string()),
+                // Pings are answered by tungstenite as the stream is read
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+async fn run_user_stream(app: AppHandle, exchange: String, current_key: Arc<Mutex<Option<String>>>) {
+    loop {
+        if let Err(e) = stream_session(&app, &exchange, &current_key).await {
+            eprintln!("User data stream for {} failed: {}", exchange, e);
+            let _ = app.emit("user-stream-error", e);
+        }
+        
+        tokio::time::sleep(Duration::from_secs(RECONNECT_DELAY_SECS)).await;
+    }
+}
+
+async fn stop_stream(stream: UserStream) {
+    stream.task.abort();
+    if let Some(key) = stream.listen_key.lock().await.take() {
+        if let Err(e) = close_listen_key(&stream.exchange, &key).await {
+            eprintln!("Failed to close listenKey: {}", e);
+        }
+    }
+}
+
+/// Stream live fills and balance changes for `exchange` as `order-update` and
+/// `account-update` events, replacing any stream already running.
+#[tauri::command]
+pub async fn start_user_stream(app: AppHandle, state: State<'_, AppState>, exchange: String) -> Result<(), String> {
+    let cfg = config::load_config()?;
+    if config::find_exchange(&cfg, &exchange).is_none() {
+        return Err(format!("Exchange not configured: {}", exchange));
+    }
+    
+    let mut current = state.user_stream.lock().await;
+    if let Some(previous) = current.take() {
+        stop_stream(previous).await;
+    }
+    
+    let listen_key = Arc::new(Mutex::new(None));
+    let task = tauri::async_runtime::spawn(run_user_stream(app, exchange.clone(), listen_key.clone()));
+    *current = Some(UserStream {
+        exchange,
+        listen_key,
+        task,
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_user_stream(state: State<'_, AppState>) -> Result<(), String> {
+    if let Some(stream) = state.user_stream.lock().await.take() {
+        stop_stream(stream).await;
+    }
+    Ok(())
+}
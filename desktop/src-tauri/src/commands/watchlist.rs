@@ -0,0 +1,102 @@
+use std::collections::HashSet;
+use tauri::State;
+
+use super::config;
+use super::prices::{self, PriceData};
+use crate::AppState;
+
+const DEFAULT_WATCHLIST: &[&str] = &[
+    "BTC/USDT",
+    "ETH/USDT",
+    "SOL/USDT",
+    "BNB/USDT",
+    "XRP/USDT",
+    "ADA/USDT",
+];
+
+/// Normalize to "BASE/QUOTE", dropping duplicates (first wins) and unrecognised symbols.
+pub(crate) fn normalize_watchlist(symbols: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    symbols
+        .iter()
+        .filter_map(|s| prices::normalize_symbol(s))
+        .filter(|s| seen.insert(s.clone()))
+        .collect()
+}
+
+/// The stored watchlist, or the default set if none has been saved.
+///
+/// Hand-edited entries are normalized and the cleaned list is written back.
+pub(crate) fn load_watchlist() -> Vec<String> {
+    let stored = config::load_config().ok().and_then(|c| c.watchlist);
+    
+    if let Some(stored) = stored.filter(|w| !w.is_empty()) {
+        let cleaned = normalize_watchlist(&stored);
+        if cleaned != stored {
+            if let Err(e) = save_watchlist(&cleaned) {
+                eprintln!("Failed to save normalized watchlist: {}", e);
+            }
+        }
+        if !cleaned.is_empty() {
+            return cleaned;
+        }
+    }
+    
+    DEFAULT_WATCHLIST.iter().map(|s| s.to_string()).collect()
+}
+
+pub(crate) fn save_watchlist(symbols: &[String]) -> Result<(), String> {
+    let mut config = config::load_config()?;
+    config.watchlist = Some(symbols.to_vec());
+    config::save_config(&config)
+}
+
+/// Sort fetched prices into the order of the watchlist.
+pub(crate) fn sort_by_watchlist(prices: &mut [PriceData], watchlist: &[String]) {
+    let position = |symbol: &str| {
+        let key = symbol.replace('/', "");
+        watchlist
+            .iter()
+            .position(|w| w.replace('/', "").eq_ignore_ascii_case(&key))
+            .unwrap_or(usize::MAX)
+    };
+    prices.sort_by_key(|p| position(&p.symbol));
+}
+
+#[tauri::command]
+pub async fn get_watchlist() -> Result<Vec<String>, String> {
+    Ok(load_watchlist())
+}
+
+#[tauri::command]
+pub async fn set_watchlist(
+    state: State<'_, AppState>,
+    symbols: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let symbols = normalize_watchlist(&symbols);
+    save_watchlist(&symbols)?;
+    state.watchlist_reload.notify_one();
+    Ok(symbols)
+}
+
+/// Persist a new order for the existing watchlist symbols.
+///
+/// Adding or removing symbols is rejected — use `set_watchlist` for that.
+#[tauri::command]
+pub async fn reorder_watchlist(
+    state: State<'_, AppState>,
+    symbols: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let current = load_watchlist();
+    let symbols = normalize_watchlist(&symbols);
+    
+    let current_set: HashSet<&String> = current.iter().collect();
+    let new_set: HashSet<&String> = symbols.iter().collect();
+    if symbols.len() != current.len() || current_set != new_set {
+        return Err("Reordered list must contain exactly the current watchlist symbols".to_string());
+    }
+    
+    save_watchlist(&symbols)?;
+    state.watchlist_reload.notify_one();
+    Ok(symbols)
+}
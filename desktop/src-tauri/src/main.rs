@@ -3,15 +3,42 @@
 
 mod commands;
 
-use commands::{alerts, config, daemon, dca, portfolio, prices};
+use commands::{
+    alerts, config, daemon, dca, exchanges, history, journal, ledger, market, mock, notifications, paper,
+    portfolio, predictions, preset, prices, recovery, reports, risk, scheduled, stream, watchlist,
+};
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Notify};
 
 #[derive(Default)]
 pub struct AppState {
     pub prices_cache: Arc<Mutex<Vec<prices::PriceData>>>,
+    pub last_updated: Arc<Mutex<Option<i64>>>,
+    pub last_emitted_hash: Arc<Mutex<Option<u64>>>,
+    pub watchlist_reload: Arc<Notify>,
+    pub ath_cache: Arc<Mutex<HashMap<String, (i64, prices::AthInfo)>>>,
+    pub fear_greed_cache: Arc<Mutex<Option<(i64, market::FearGreed)>>>,
+    pub server_time_offset: Arc<Mutex<Option<(i64, i64)>>>,
+    pub sparklines: Arc<Mutex<HashMap<String, VecDeque<f64>>>>,
+    pub exchange_status: Arc<Mutex<Option<(i64, exchanges::ExchangeStatus)>>>,
+    pub exchange_info: Arc<Mutex<Option<(i64, prices::ExchangeInfo)>>>,
+    pub extended_changes: Arc<Mutex<HashMap<String, (i64, prices::ExtendedChanges)>>>,
+    pub account_cache: Arc<Mutex<HashMap<String, (i64, serde_json::Value)>>>,
+    pub fee_tiers: Arc<Mutex<HashMap<String, exchanges::FeeTier>>>,
+    pub key_permissions: Arc<Mutex<HashMap<String, exchanges::KeyPermissions>>>,
+    pub signed_semaphores: Arc<Mutex<exchanges::SignedSemaphores>>,
+    pub last_signed_timestamp: Arc<Mutex<i64>>,
+    pub alert_guard: Arc<Mutex<alerts::AlertGuard>>,
+    pub alert_evaluations: Arc<Mutex<alerts::EvaluationLog>>,
+    pub pending_alerts: Arc<Mutex<notifications::PendingAlerts>>,
+    pub rate_limits: Arc<Mutex<Vec<prices::RateLimit>>>,
+    pub user_stream: Arc<Mutex<Option<stream::UserStream>>>,
+    pub price_loop: Arc<Mutex<Option<prices::PriceLoop>>>,
+    pub feed_metrics: Arc<Mutex<prices::FeedTracker>>,
+    pub correlation_cache: Arc<Mutex<HashMap<String, (i64, risk::CorrelationMatrix)>>>,
 }
 
 fn main() {
@@ -21,67 +48,324 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             // Prices
             prices::get_prices,
+            prices::get_cached_prices,
+            prices::get_ath_distance,
+            prices::get_price_sparkline,
+            prices::get_min_buy,
+            prices::get_extended_changes,
+            prices::suggest_watchlist,
+            prices::get_rate_limits,
+            prices::restart_price_loop,
+            prices::get_feed_metrics,
+            prices::split_symbol,
+            // Ledger
+            ledger::get_asset_cost_basis,
+            ledger::get_realized_lots,
+            ledger::find_rapid_round_trips,
+            ledger::recompute_pnl_with_fees,
+            // Market
+            market::get_fear_greed,
+            // Watchlist
+            watchlist::get_watchlist,
+            watchlist::set_watchlist,
+            watchlist::reorder_watchlist,
             // Portfolio
             portfolio::get_paper_portfolio,
             portfolio::get_live_portfolio,
+            portfolio::get_portfolio_change_24h,
+            portfolio::estimate_exit_value,
+            portfolio::best_acquisition_route,
+            portfolio::amount_to_reach_allocation,
+            portfolio::recommend_rebalance_band,
+            portfolio::get_holding_performance,
+            portfolio::get_portfolio_break_even,
+            portfolio::get_total_pnl,
+            portfolio::get_unified_position,
+            // Paper trading
+            paper::execute_paper_trade,
+            paper::get_available_to_trade,
+            paper::get_trades_remaining_today,
+            paper::get_idle_yield_estimate,
+            paper::preview_average_down,
+            paper::validate_paper_wallet,
+            paper::repair_paper_wallet,
+            paper::consolidate_holdings,
+            paper::get_dust,
+            paper::consolidate_dust,
+            paper::reset_paper_wallet,
+            paper::deposit_usdt,
+            paper::withdraw_usdt,
+            // Scheduled orders
+            scheduled::schedule_order,
+            scheduled::cancel_scheduled_order,
+            scheduled::list_scheduled_orders,
             // Alerts
             alerts::get_alerts,
+            alerts::get_alert_history,
             alerts::add_alert,
+            alerts::add_alert_from_level,
             alerts::remove_alert,
+            alerts::simulate_alert,
+            alerts::evaluate_alerts_now,
+            alerts::get_alert_evaluation_state,
+            alerts::get_alert_diagnostics,
+            alerts::estimate_time_to_target,
+            alerts::set_alerts_enabled_for_symbol,
             // Config
             config::get_config,
             config::save_exchange,
+            config::check_config_security,
+            config::harden_config_permissions,
+            config::merge_duplicate_exchanges,
+            config::get_allocation_caps,
+            config::set_allocation_cap,
+            // Exchanges
+            exchanges::list_supported_exchanges,
+            exchanges::get_clock_skew,
+            exchanges::get_exchange_status,
+            exchanges::get_free_balance,
+            exchanges::get_fee_tier,
+            exchanges::audit_exchanges,
+            exchanges::import_exchanges,
+            exchanges::get_key_permissions,
+            // User data stream
+            stream::start_user_stream,
+            stream::stop_user_stream,
             // Daemon
             daemon::get_daemon_status,
             daemon::start_daemon,
             daemon::stop_daemon,
             daemon::get_daemon_log,
+            daemon::get_daemon_config,
+            daemon::set_daemon_config,
+            daemon::reload_daemon,
             // DCA
             dca::get_dca_configs,
             dca::toggle_dca,
+            dca::suggest_dca_amount,
+            dca::get_dca_annual_projection,
+            dca::compare_dca_vs_lumpsum,
+            dca::recommend_dca_frequency,
+            // History
+            history::get_cash_drag,
+            history::snapshot_portfolio,
+            history::change_since_snapshot,
+            history::compare_composition,
+            history::prune_portfolio_history,
+            history::downsample_portfolio_history,
+            history::get_max_drawdown,
+            history::get_pnl_series,
+            history::get_twr,
+            history::get_daily_summaries,
+            history::get_sharpe_ratio,
+            history::get_cvar,
+            // Journal
+            journal::add_journal_entry,
+            journal::get_journal,
+            journal::remove_journal_entry,
+            // Mock prices
+            mock::set_mock_prices,
+            // Notifications
+            notifications::set_dnd,
+            notifications::clear_dnd,
+            notifications::send_test_notification,
+            notifications::validate_webhook,
+            // Presets
+            preset::export_preset,
+            preset::import_preset,
+            // Recovery
+            recovery::restore_from_backup,
+            recovery::list_backups,
+            recovery::restore_backup,
+            // Reports
+            reports::send_report_now,
+            // Predictions
+            predictions::add_prediction,
+            predictions::get_predictions,
+            predictions::get_prediction_accuracy,
+            // Risk
+            risk::compute_position_size,
+            risk::round_trip_cost,
+            risk::get_exposure,
+            risk::get_volatility,
+            risk::get_correlation_matrix,
+            risk::get_exposure_by_quote,
+            risk::get_concentration,
+            risk::simulate_scenario,
         ])
         .setup(|app| {
             let handle = app.handle().clone();
+            recovery::init(handle.clone());
             
-            // Spawn background task for price updates
+            // Bootstrap ~/.omnitrade with default data files on first run
+            if let Err(e) = recovery::init_app_data() {
+                eprintln!("Failed to initialize app data: {}", e);
+            }
+            
+            // Spawn background task for price updates, tracked so it can be restarted
+            let price_loop = spawn_price_loop(handle);
+            if let Ok(mut slot) = app.state::<AppState>().price_loop.try_lock() {
+                *slot = Some(price_loop);
+            }
+            
+            // Spawn background task for portfolio snapshots
+            tauri::async_runtime::spawn(history::snapshot_loop());
+            
+            // Spawn background task for scheduled orders (also catches up on missed ones)
+            let scheduled_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
-                price_update_loop(handle).await;
+                scheduled::scheduled_orders_loop(scheduled_handle).await;
             });
             
+            // Spawn background task for scheduled portfolio reports
+            let report_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                reports::report_loop(report_handle).await;
+            });
+            
+            // Spawn background task scoring predictions past their deadline
+            tauri::async_runtime::spawn(predictions::prediction_loop());
+            
             Ok(())
         })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
-async fn price_update_loop(app: AppHandle) {
-    let symbols = vec![
-        "BTCUSDT".to_string(),
-        "ETHUSDT".to_string(),
-        "SOLUSDT".to_string(),
-        "BNBUSDT".to_string(),
-        "XRPUSDT".to_string(),
-        "ADAUSDT".to_string(),
-    ];
-    
+/// Feed the tick's health into the alert guard, announcing suspend/resume transitions.
+///
+/// Suspension is also sent as a critical notification: price alerts silently stop
+/// firing until the feed recovers, so channels that bypass DND should hear about it.
+async fn record_feed_health(app: &AppHandle, anomalies: usize, fetch_failed: bool) {
+    if let Some(state) = app.try_state::<AppState>() {
+        let now = chrono::Utc::now().timestamp_millis();
+        let change = state.alert_guard.lock().await.record_tick(now, anomalies, fetch_failed);
+        if let Some(change) = change {
+            let event = if change.suspended { "alerts-suspended" } else { "alerts-resumed" };
+            let _ = app.emit(event, &change);
+            if change.suspended {
+                let reason = change.reason.as_deref().unwrap_or("the price feed looks unreliable");
+                let message = format!("Price alerts are paused: {}", reason);
+                notifications::dispatch(app, "Alerts suspended", &message, true).await;
+            }
+        }
+    }
+}
+
+/// Start a price loop that exits when its shutdown signal is notified.
+pub(crate) fn spawn_price_loop(app: AppHandle) -> prices::PriceLoop {
+    let shutdown = Arc::new(Notify::new());
+    let task = tauri::async_runtime::spawn(price_update_loop(app, shutdown.clone()));
+    prices::PriceLoop { shutdown, task }
+}
+
+async fn price_update_loop(app: AppHandle, shutdown: Arc<Notify>) {
     loop {
-        match prices::fetch_prices_from_binance(&symbols).await {
-            Ok(price_data) => {
-                // Emit price update event to frontend
-                let _ = app.emit("prices-update", &price_data);
+        // Re-read the watchlist every tick so edits apply without a restart
+        let watchlist = watchlist::load_watchlist();
+        let symbols: Vec<String> = watchlist.iter().map(|s| prices::to_binance_symbol(s)).collect();
+        let cfg = config::load_config();
+        if let Ok(cfg) = &cfg {
+            // REST calls use the API base cached here rather than re-reading config.json each time
+            exchanges::refresh_api_base(cfg);
+        }
+        let cfg = cfg.unwrap_or_default();
+        
+        // Offline mode replays fixture frames, one per tick
+        mock::advance();
+        
+        let started = std::time::Instant::now();
+        let fetched = prices::fetch_prices_from_binance(&symbols).await;
+        if let Some(state) = app.try_state::<AppState>() {
+            let latency_ms = started.elapsed().as_millis() as u64;
+            let error = fetched.as_ref().err().cloned();
+            state.feed_metrics.lock().await.record(chrono::Utc::now().timestamp_millis(), latency_ms, error);
+        }
+        
+        match fetched {
+            Ok(mut price_data) => {
+                watchlist::sort_by_watchlist(&mut price_data, &watchlist);
+                let mut anomalous: Vec<String> = Vec::new();
                 
-                // Update cache in state
                 if let Some(state) = app.try_state::<AppState>() {
+                    // Only emit to the frontend when the payload actually changed
+                    let hash = prices::hash_prices(&price_data);
+                    let mut last_hash = state.last_emitted_hash.lock().await;
+                    if *last_hash != Some(hash) {
+                        let _ = app.emit("prices-update", &price_data);
+                        *last_hash = Some(hash);
+                    }
+                    
+                    // Update cache in state
                     let mut cache = state.prices_cache.lock().await;
-                    *cache = price_data;
+                    
+                    // Optional fine-grained events for components subscribed to one symbol
+                    if cfg.per_symbol_price_events.unwrap_or(false) {
+                        let epsilon = cfg.price_event_epsilon_percent.unwrap_or(0.0);
+                        for p in prices::changed_prices(&cache, &price_data, epsilon) {
+                            let _ = app.emit(&format!("price-update:{}", p.symbol), p);
+                        }
+                    }
+                    
+                    *cache = price_data.clone();
+                    
+                    // Flag single-tick jumps (bad prints) against the previous buffered price
+                    let mut sparklines = state.sparklines.lock().await;
+                    if let Some(max_jump) = cfg.max_tick_jump_percent {
+                        for anomaly in prices::detect_anomalies(&sparklines, &price_data, max_jump) {
+                            let _ = app.emit("price-anomaly", &anomaly);
+                            anomalous.push(anomaly.symbol);
+                        }
+                    }
+                    prices::record_sparklines(&mut sparklines, &price_data);
+                    *state.last_updated.lock().await = Some(chrono::Utc::now().timestamp_millis());
+                }
+                
+                // Suspend alerts entirely while the feed is unreliable
+                record_feed_health(&app, anomalous.len(), false).await;
+                
+                // Keep a momentary wick from firing alerts unless configured otherwise
+                if cfg.anomaly_skip_alerts.unwrap_or(true) {
+                    price_data.retain(|p| !anomalous.contains(&p.symbol));
+                }
+                if let Err(e) = alerts::evaluate_alerts(&app, &price_data).await {
+                    eprintln!("Failed to evaluate alerts: {}", e);
                 }
             }
             Err(e) => {
                 eprintln!("Failed to fetch prices: {}", e);
+                record_feed_health(&app, 0, true).await;
             }
         }
         
-        // Wait 5 seconds before next update
-        tokio::time::sleep(Duration::from_secs(5)).await;
+        // Back off while Binance reports maintenance, and tell the UI why prices are stale
+        let mut interval = prices::PRICE_POLL_INTERVAL_SECS;
+        if let Some(state) = app.try_state::<AppState>() {
+            if let Ok(status) = exchanges::exchange_status(&state).await {
+                if status.status == "maintenance" {
+                    let _ = app.emit("exchange-maintenance", &status);
+                    interval = prices::MAINTENANCE_POLL_INTERVAL_SECS;
+                }
+            }
+        }
+        
+        // Wait before next update, waking early if the watchlist changed; a shutdown
+        // sent mid-tick is stored by Notify and picked up here
+        let sleep = tokio::time::sleep(Duration::from_secs(interval));
+        match app.try_state::<AppState>() {
+            Some(state) => {
+                tokio::select! {
+                    _ = sleep => {}
+                    _ = state.watchlist_reload.notified() => {}
+                    _ = shutdown.notified() => return,
+                }
+            }
+            None => {
+                tokio::select! {
+                    _ = sleep => {}
+                    _ = shutdown.notified() => return,
+                }
+            }
+        }
     }
 }
@@ -25,6 +25,13 @@ interface Config {
   };
 }
 
+interface ConfigSecurity {
+  path: string;
+  exists: boolean;
+  fileMode?: string;
+  secure: boolean;
+}
+
 interface DaemonStatus {
   running: boolean;
   pid?: number;
@@ -34,6 +41,7 @@ interface DaemonStatus {
 export const Settings: React.FC = () => {
   const [config, setConfig] = useState<Config | null>(null);
   const [daemonStatus, setDaemonStatus] = useState<DaemonStatus>({ running: false });
+  const [configSecurity, setConfigSecurity] = useState<ConfigSecurity | null>(null);
   const [showAddExchange, setShowAddExchange] = useState(false);
   const [editingExchange, setEditingExchange] = useState<string | null>(null);
   const [newExchange, setNewExchange] = useState({
@@ -55,11 +63,20 @@ export const Settings: React.FC = () => {
       ]);
       setConfig(configData);
       setDaemonStatus(statusData);
+      setConfigSecurity(await invoke<ConfigSecurity>('check_config_security'));
     } catch (error) {
       console.error('Failed to load settings:', error);
     }
   };
 
+  const handleHardenConfig = async () => {
+    try {
+      setConfigSecurity(await invoke<ConfigSecurity>('harden_config_permissions'));
+    } catch (error) {
+      console.error('Failed to fix config permissions:', error);
+    }
+  };
+
   const handleSaveExchange = async () => {
     try {
       await invoke('save_exchange', {
@@ -115,6 +132,21 @@ export const Settings: React.FC = () => {
           </button>
         </div>
         <div className="panel-content">
+          {configSecurity && configSecurity.exists && !configSecurity.secure && (
+            <div className="exchange-item">
+              <div className="exchange-info">
+                <span className="badge badge-red">insecure</span>
+                <span className="text-muted">
+                  {configSecurity.path} is readable by other users (mode {configSecurity.fileMode})
+                </span>
+              </div>
+              <div className="exchange-actions">
+                <button className="btn btn-sm btn-danger" onClick={handleHardenConfig}>
+                  Fix permissions
+                </button>
+              </div>
+            </div>
+          )}
           {config && Object.keys(config.exchanges).length > 0 ? (
             <div className="exchanges-list">
               {Object.entries(config.exchanges).map(([name, exchange]) => (
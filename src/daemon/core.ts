@@ -20,6 +20,7 @@ import ccxt, { type Exchange } from 'ccxt';
 import { loadConfig } from '../config/loader.js';
 import { writePid, removePid } from './pid.js';
 import { sendNotification } from '../notifications/index.js';
+import { loadWallet } from '../paper/wallet.js';
 
 // ============================================
 // Types
@@ -34,6 +35,7 @@ interface PriceAlert {
   createdAt: number;
   triggered?: boolean;
   triggeredAt?: number;
+  enabled?: boolean; // set by the desktop app; missing means enabled
 }
 
 interface AlertsData {
@@ -153,6 +155,66 @@ function isDCADue(dca: DCAConfig, now: number): boolean {
 // DCA poll function
 // ============================================
 
+/**
+ * Why a DCA buy of `spend` USDT of `asset` must be skipped, or null if it may go ahead.
+ * Applies the same USDT reserve and allocation caps as desktop paper buys, checked
+ * against the exchange balance for real orders and the paper wallet for simulated ones.
+ */
+async function dcaGuardViolation(
+  exchange: Exchange,
+  hasCredentials: boolean,
+  asset: string,
+  spend: number,
+  price: number,
+  config: Awaited<ReturnType<typeof loadConfig>>
+): Promise<string | null> {
+  const reserve = config.security?.minUsdtReserve ?? 0;
+  const caps = config.allocationCaps;
+  const cap = caps?.maxAllocationPercent[asset] ?? caps?.defaultMaxAllocationPercent;
+  if (reserve <= 0 && cap === undefined) return null;
+
+  // Units held per asset, USDT included
+  const held: Record<string, number> = {};
+  let usdt: number;
+  if (hasCredentials) {
+    const balance = await exchange.fetchBalance();
+    for (const [a, total] of Object.entries((balance.total ?? {}) as unknown as Record<string, number>)) {
+      if (total > 0) held[a.toUpperCase()] = total;
+    }
+    // Only free USDT can be spent
+    usdt = ((balance.free ?? {}) as unknown as Record<string, number>).USDT ?? 0;
+  } else {
+    const wallet = loadWallet();
+    for (const h of Object.values(wallet.holdings)) held[h.asset.toUpperCase()] = h.amount;
+    usdt = wallet.usdt;
+  }
+
+  if (reserve > 0 && usdt - spend < reserve) {
+    return `would breach the $${reserve.toFixed(2)} USDT reserve (available: $${Math.max(usdt - reserve, 0).toFixed(2)})`;
+  }
+  if (cap === undefined) return null;
+
+  let total = usdt;
+  for (const [a, amount] of Object.entries(held)) {
+    if (a === 'USDT') continue;
+    if (a === asset) {
+      total += amount * price;
+      continue;
+    }
+    try {
+      const ticker = await exchange.fetchTicker(`${a}/USDT`);
+      total += amount * (ticker.last ?? 0);
+    } catch {
+      // Unlisted assets are left out of the total, as on the desktop
+    }
+  }
+  const projected = total > 0 ? (((held[asset] ?? 0) * price + spend) / total) * 100 : 100;
+  if (projected > cap) {
+    return `would take ${asset} to ${projected.toFixed(1)}% of the portfolio, above its ${cap.toFixed(1)}% cap`;
+  }
+  return null;
+}
+
 async function pollAndCheckDCAs(
   exchanges: Map<string, Exchange>,
   config: Awaited<ReturnType<typeof loadConfig>>
@@ -191,6 +253,14 @@ async function pollAndCheckDCAs(
       const exchCfg = config.exchanges[dca.exchange];
       const hasCredentials = !!(exchCfg?.apiKey && exchCfg?.secret);
 
+      const baseAsset = (dca.symbol.split('/')[0] ?? dca.symbol).toUpperCase();
+      const violation = await dcaGuardViolation(exchange, hasCredentials, baseAsset, dca.amountUSD, price, config);
+      if (violation) {
+        // Left due, so it goes ahead on a later poll once the balance allows it
+        log(`  ⚠ DCA ${dca.id}: buy ${violation} — skipping`);
+        continue;
+      }
+
       let spent = dca.amountUSD;
 
       if (hasCredentials) {
@@ -215,7 +285,6 @@ async function pollAndCheckDCAs(
       dca.totalSpent += spent;
 
       // Send notification
-      const baseAsset = dca.symbol.split('/')[0] ?? dca.symbol;
       const title = `OmniTrade DCA: ${baseAsset}`;
       const message = `DCA executed: bought $${dca.amountUSD} of ${baseAsset} at $${price.toFixed(2)} on ${dca.exchange}`;
 
@@ -262,7 +331,7 @@ async function pollAndCheckAlerts(
   config: Awaited<ReturnType<typeof loadConfig>>
 ): Promise<void> {
   const data = await loadAlerts();
-  const activeAlerts = data.alerts.filter((a) => !a.triggered);
+  const activeAlerts = data.alerts.filter((a) => !a.triggered && a.enabled !== false);
 
   if (activeAlerts.length === 0) {
     log(`Poll complete — no active alerts`);
@@ -370,7 +439,7 @@ export async function startDaemon(): Promise<void> {
     process.exit(1);
   }
 
-  const pollInterval = (config.daemon?.pollInterval ?? 60) * 1000; // Convert to ms
+  let pollInterval = (config.daemon?.pollInterval ?? 60) * 1000; // Convert to ms
   log(`Poll interval: ${config.daemon?.pollInterval ?? 60}s`);
 
   const enabledChannels: string[] = [];
@@ -404,7 +473,7 @@ export async function startDaemon(): Promise<void> {
   }
 
   // Schedule regular polling
-  const timer = setInterval(async () => {
+  const poll = async () => {
     try {
       await pollAndCheckAlerts(exchanges, config);
     } catch (err) {
@@ -415,12 +484,31 @@ export async function startDaemon(): Promise<void> {
     } catch (err) {
       log(`DCA poll error: ${(err as Error).message}`);
     }
-  }, pollInterval);
+  };
+  let timer = setInterval(poll, pollInterval);
 
   // Keep the process alive
   timer.unref(); // Allow process to exit if nothing else is holding it
   // But we still want it running, so re-ref it:
   timer.ref();
 
+  // SIGHUP reloads config in place (sent by the desktop app's reload_daemon).
+  // New exchanges still require a restart; everything else applies on the next poll.
+  process.on('SIGHUP', () => {
+    try {
+      config = loadConfig();
+    } catch (err) {
+      log(`Config reload failed, keeping previous config: ${(err as Error).message}`);
+      return;
+    }
+    const newInterval = (config.daemon?.pollInterval ?? 60) * 1000;
+    if (newInterval !== pollInterval) {
+      clearInterval(timer);
+      pollInterval = newInterval;
+      timer = setInterval(poll, pollInterval);
+    }
+    log(`Config reloaded (SIGHUP) — poll interval ${pollInterval / 1000}s`);
+  });
+
   log(`Daemon running — next poll in ${config.daemon?.pollInterval ?? 60}s`);
 }
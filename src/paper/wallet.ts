@@ -12,6 +12,7 @@ import { join } from 'path';
 
 const WALLET_DIR = join(homedir(), '.omnitrade');
 const WALLET_PATH = join(WALLET_DIR, 'paper-wallet.json');
+const CONFIG_PATH = join(WALLET_DIR, 'config.json');
 const INITIAL_USDT = 10_000;
 const FEE_RATE = 0.001; // 0.1% (Binance spot taker fee)
 const BINANCE_API = 'https://api.binance.com/api/v3';
@@ -197,12 +198,43 @@ export interface TradeResult {
   wallet: PaperWallet;
 }
 
+/**
+ * security.maxTradesPerDay from the shared config, if set. Paper trading works
+ * without a config file, so a missing or unreadable one means no limit.
+ */
+function readMaxTradesPerDay(): number | undefined {
+  try {
+    const config = JSON.parse(readFileSync(CONFIG_PATH, 'utf-8'));
+    const limit = config?.security?.maxTradesPerDay;
+    return typeof limit === 'number' && limit > 0 ? limit : undefined;
+  } catch {
+    return undefined;
+  }
+}
+
+/**
+ * Refuse a trade once today's count (local calendar day) reaches the daily limit.
+ * Counted from the same ledger the desktop app uses, so both share one budget.
+ */
+function assertTradeLimit(wallet: PaperWallet): void {
+  const limit = readMaxTradesPerDay();
+  if (limit === undefined) return;
+
+  const startOfDay = new Date();
+  startOfDay.setHours(0, 0, 0, 0);
+  const count = wallet.trades.filter((t) => t.timestamp >= startOfDay.getTime()).length;
+  if (count >= limit) {
+    throw new Error(`Daily trade limit reached (${count} of ${limit} trades today)`);
+  }
+}
+
 export async function executeBuy(
   wallet: PaperWallet,
   asset: string,
   amount: number
 ): Promise<TradeResult> {
   if (amount <= 0) throw new Error('Amount must be positive');
+  assertTradeLimit(wallet);
 
   const assetUpper = asset.toUpperCase();
   const price = await fetchCurrentPrice(assetUpper);
@@ -257,6 +289,7 @@ export async function executeSell(
   amount: number
 ): Promise<TradeResult> {
   if (amount <= 0) throw new Error('Amount must be positive');
+  assertTradeLimit(wallet);
 
   const assetUpper = asset.toUpperCase();
   const holding = wallet.holdings[assetUpper];
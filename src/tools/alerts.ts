@@ -18,6 +18,7 @@ interface PriceAlert {
   createdAt: number;
   triggered?: boolean;
   triggeredAt?: number;
+  enabled?: boolean; // set by the desktop app; missing means enabled
 }
 
 interface AlertsData {
@@ -75,8 +76,8 @@ async function checkAlerts(
   const data = await loadAlerts();
   const triggered: PriceAlert[] = [];
 
-  // Get active (non-triggered) alerts
-  const activeAlerts = data.alerts.filter((a) => !a.triggered);
+  // Get active (non-triggered, not disabled) alerts
+  const activeAlerts = data.alerts.filter((a) => !a.triggered && a.enabled !== false);
 
   if (activeAlerts.length === 0) {
     return [];
@@ -221,6 +222,7 @@ export function registerAlertTools(
             symbol: a.symbol,
             condition: `${a.condition} $${a.targetPrice.toFixed(2)}`,
             exchange: a.exchange || 'all',
+            enabled: a.enabled !== false,
             created: new Date(a.createdAt).toISOString(),
           })),
           recentlyTriggered: triggeredAlerts
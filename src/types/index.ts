@@ -17,6 +17,8 @@ export const SecurityConfigSchema = z.object({
   allowedPairs: z.array(z.string()).optional(), // Whitelist of trading pairs
   testnetOnly: z.boolean().default(false), // Force testnet mode globally
   disableWithdrawals: z.boolean().default(true), // Extra safety (we don't support withdrawals anyway)
+  minUsdtReserve: z.number().nonnegative().optional(), // USDT paper and daemon DCA buys never spend into
+  maxTradesPerDay: z.number().int().positive().optional(), // Paper trades (desktop + CLI) per local day; live orders aren't counted
 });
 
 export const NotificationConfigSchema = z.object({
@@ -39,12 +41,18 @@ export const DaemonConfigSchema = z.object({
   logFile: z.string().optional(), // custom log path (default: ~/.omnitrade/daemon.log)
 });
 
+export const AllocationCapsSchema = z.object({
+  maxAllocationPercent: z.record(z.string(), z.number()).default({}), // Per-asset caps in percent, e.g. { BTC: 25 }
+  defaultMaxAllocationPercent: z.number().optional(), // Cap for assets without their own entry
+});
+
 export const ConfigSchema = z.object({
   exchanges: z.record(z.string(), ExchangeConfigSchema),
   security: SecurityConfigSchema.optional(),
   defaultExchange: z.string().optional(),
   notifications: NotificationConfigSchema.optional(),
   daemon: DaemonConfigSchema.optional(),
+  allocationCaps: AllocationCapsSchema.optional(), // Set from the desktop app; applied to daemon DCA buys
 });
 
 export type Config = z.infer<typeof ConfigSchema>;
@@ -52,6 +60,7 @@ export type ExchangeConfig = z.infer<typeof ExchangeConfigSchema>;
 export type SecurityConfig = z.infer<typeof SecurityConfigSchema>;
 export type NotificationConfig = z.infer<typeof NotificationConfigSchema>;
 export type DaemonConfig = z.infer<typeof DaemonConfigSchema>;
+export type AllocationCaps = z.infer<typeof AllocationCapsSchema>;
 
 // ============================================
 // Response Types